@@ -14,6 +14,11 @@ pub(super) fn generate_get_args_impl(
                 pushes.extend(quote_spanned! {arg.span=>
                     res.push(#arg);
                 });
+                if let Some(negation) = arg.negation() {
+                    pushes.extend(quote_spanned! {arg.span=>
+                        res.push(#negation);
+                    });
+                }
             }
             ClapInitialization::Flatten(struct_type) => {
                 pushes.extend(quote! {
@@ -87,7 +87,33 @@ pub(super) fn gen_clap_matches(debug_cmd_input: Option<TokenStream>) -> TokenStr
     }
 }
 
-pub(super) fn gen_env_data() -> TokenStream {
+pub(super) fn gen_env_data(dotenv: Option<DotenvInfo>) -> TokenStream {
+    let dotenv = match dotenv {
+        None => TokenStream::new(),
+        Some(DotenvInfo {
+            span,
+            clap_long,
+            env_key,
+            default_path,
+            is_optional,
+        }) => quote_spanned! {span=>
+            let clap_long: ::std::option::Option<&::std::primitive::str> = #clap_long;
+            let env_key: ::std::option::Option<::std::string::String> = #env_key;
+            let default_path: ::std::option::Option<::std::string::String> = #default_path;
+
+            let dotenv_path = clap_long
+                .and_then(|clap_long| clap_data.get_one::<::std::string::String>(clap_long).cloned())
+                .or_else(|| env_key.and_then(|env_key| res.get(&env_key.to_lowercase()).cloned()));
+            let (dotenv_path, optional) = match dotenv_path {
+                ::std::option::Option::Some(path) => (::std::option::Option::Some(path), false),
+                ::std::option::Option::None => (default_path, #is_optional),
+            };
+            if let ::std::option::Option::Some(path) = dotenv_path {
+                ::config_manager::__private::extend_with_dotenv(&mut res, &path, optional)?;
+            }
+        },
+    };
+
     quote! {
         {
             (|| -> ::std::result::Result::<::config_manager::__private::EnvData, ::config_manager::Error> {
@@ -109,12 +135,104 @@ pub(super) fn gen_env_data() -> TokenStream {
                         })?,
                     );
                 }
+                #dotenv
                 ::std::result::Result::Ok(::config_manager::__private::EnvData::from(res))
             })()
         }
     }
 }
 
+/// Binds `config_file_data` taken from the options or collected by `collect_config_files`.
+/// The profile (if any) is applied to the data and `checks` are run after it in both cases.
+pub(super) fn gen_config_file_data_binding(
+    collect_config_files: TokenStream,
+    profiles: Option<&ProfilesInfo>,
+    checks: TokenStream,
+) -> TokenStream {
+    match gen_profile(profiles) {
+        None => quote! {
+            let config_file_data = &match config_file_data {
+                ::std::option::Option::Some(data) => data,
+                ::std::option::Option::None => #collect_config_files?
+            };
+            #checks
+        },
+        Some(profile) => quote! {
+            let mut config_file_data = match config_file_data {
+                ::std::option::Option::Some(data) => data,
+                ::std::option::Option::None => #collect_config_files?
+            };
+            #profile
+            let config_file_data = &config_file_data;
+            #checks
+        },
+    }
+}
+
+/// Prefixed environment variables that are not consumed by any field are bound to `unused_env`
+/// or reported as an error. Environment prefix is the same the `env` sources use.
+pub(super) fn gen_unused_env_check(
+    unused_env: Option<bool>,
+    env_keys: &[TokenStream],
+) -> TokenStream {
+    let deny = match unused_env {
+        None => return TokenStream::new(),
+        Some(deny) => deny,
+    };
+    let binary_name = binary_name();
+    quote! {
+        let unused_env = {
+            let prefix = match env_prefix.clone() {
+                ::std::option::Option::Some(prefix) => prefix,
+                ::std::option::Option::None => #binary_name?,
+            };
+            let keys: ::std::vec::Vec<::std::vec::Vec<::std::string::String>> = ::std::vec![#(#env_keys),*];
+            ::config_manager::__private::check_unused_env(env_data, &prefix, &keys.concat(), #deny)?
+        };
+    }
+}
+
+/// `ConfigOption::DenyUnknownKeys` takes precedence over the `deny_unknown_keys` attribute.
+pub(super) fn gen_unknown_keys_check(
+    deny_unknown_keys: bool,
+    config_keys: &[TokenStream],
+) -> TokenStream {
+    quote! {
+        if deny_unknown_keys.unwrap_or(#deny_unknown_keys) {
+            let keys: ::std::vec::Vec<::std::vec::Vec<::std::string::String>> = ::std::vec![#(#config_keys),*];
+            ::config_manager::__private::check_unknown_keys(config_file_data, &keys.concat())?;
+        }
+    }
+}
+
+/// Selects the profile by `clap`, `env` or `default` and applies it to `config_file_data`.
+/// Only the profile selected by `clap` or `env` must exist.
+fn gen_profile(profiles: Option<&ProfilesInfo>) -> Option<TokenStream> {
+    let ProfilesInfo {
+        span,
+        clap_long,
+        env_key,
+        default,
+    } = profiles?;
+
+    Some(quote_spanned! {*span=>
+        {
+            let clap_long: ::std::option::Option<&::std::primitive::str> = #clap_long;
+            let env_key: ::std::option::Option<::std::string::String> = #env_key;
+            let default_profile: ::std::option::Option<::std::string::String> = #default;
+
+            let selected_profile = clap_long
+                .and_then(|clap_long| clap_data.get_one::<::std::string::String>(clap_long).cloned())
+                .or_else(|| env_key.and_then(|env_key| env_data.get(env_key.as_str()).cloned()));
+            ::config_manager::__private::apply_profile(
+                &mut config_file_data,
+                selected_profile.as_deref(),
+                default_profile.as_deref(),
+            )?;
+        }
+    })
+}
+
 pub(super) fn gen_config_file_data(config_keys: Vec<ConfigFileInfo>) -> TokenStream {
     let env_data = quote!(env_data);
     let matches = quote!(clap_data);
@@ -122,36 +240,160 @@ pub(super) fn gen_config_file_data(config_keys: Vec<ConfigFileInfo>) -> TokenStr
     let mut config_paths_init = TokenStream::new();
     for ConfigFileInfo {
         span,
-        file_format,
+        kind,
         env_key,
         clap_long,
         is_optional,
-        default_path,
+        is_multiple,
+        discover,
+        search_upwards,
+        default_paths,
     } in config_keys
     {
+        let discover_format = match &kind {
+            ConfigFileKind::File {
+                format: Some(format),
+            } => quote_spanned!(span=> ::std::option::Option::Some(#format)),
+            _ => quote_spanned!(span=> ::std::option::Option::None),
+        };
+        let file_options = match kind {
+            ConfigFileKind::File { format } => {
+                let format = match format {
+                    Some(format) => format,
+                    None => {
+                        quote_spanned!(span=> ::config_manager::__private::detect_format(&path)?)
+                    }
+                };
+                quote_spanned! {span=>
+                    |path: ::std::string::String| -> ::std::result::Result<_, ::config_manager::Error> {
+                        ::std::result::Result::Ok(::std::option::Option::Some(
+                            ::config_manager::FileOptions::File { format: #format, path }
+                        ))
+                    }
+                }
+            }
+            ConfigFileKind::Directory { glob } => {
+                let skip_missing = if is_optional {
+                    quote_spanned! {span=>
+                        if !::std::path::Path::new(&path).is_dir() {
+                            return ::std::result::Result::Ok(::std::option::Option::None);
+                        }
+                    }
+                } else {
+                    TokenStream::new()
+                };
+                quote_spanned! {span=>
+                    |path: ::std::string::String| -> ::std::result::Result<_, ::config_manager::Error> {
+                        #skip_missing
+                        ::std::result::Result::Ok(::std::option::Option::Some(
+                            ::config_manager::FileOptions::Directory { path, glob: #glob }
+                        ))
+                    }
+                }
+            }
+        };
+
+        let (from_clap, from_env) = if is_multiple {
+            (
+                quote_spanned! {span=>
+                    if let Some(field_matches) = #matches.get_many::<::std::string::String>(clap_long) {
+                        for path in field_matches {
+                            res.extend(file_options(path.to_string())?);
+                        }
+                        return ::std::result::Result::Ok(());
+                    }
+                },
+                quote_spanned! {span=>
+                    if let ::std::option::Option::Some(paths) = #env_data.get(env_key) {
+                        for path in ::std::env::split_paths(paths) {
+                            if !path.as_os_str().is_empty() {
+                                res.extend(file_options(path.to_string_lossy().into_owned())?);
+                            }
+                        }
+                        return ::std::result::Result::Ok(());
+                    }
+                },
+            )
+        } else {
+            (
+                quote_spanned! {span=>
+                    if let Some(field_match) = #matches.get_one::<::std::string::String>(clap_long) {
+                        res.extend(file_options(field_match.to_string())?);
+                        return ::std::result::Result::Ok(());
+                    }
+                },
+                quote_spanned! {span=>
+                    let from_env = #env_data.get(env_key);
+                    if let ::std::option::Option::Some(path) = from_env {
+                        res.extend(file_options(path.clone())?);
+                        return ::std::result::Result::Ok(());
+                    }
+                },
+            )
+        };
+
+        let discovered = match discover {
+            None => TokenStream::new(),
+            Some(discover) => {
+                let name = match discover {
+                    Some(name) => name,
+                    None => {
+                        let binary_name = binary_name();
+                        quote_spanned!(span=> #binary_name?)
+                    }
+                };
+                quote_spanned! {span=>
+                    if let ::std::option::Option::Some(file) =
+                        ::config_manager::__private::discover_config_file(&#name, #discover_format, #env_data)
+                    {
+                        res.push(file);
+                        return ::std::result::Result::Ok(());
+                    }
+                    err_msg.push("file was not discovered in the config directories".into());
+                }
+            }
+        };
+
+        let found_upwards = match search_upwards {
+            None => TokenStream::new(),
+            Some(name) => quote_spanned! {span=>
+                let found = ::config_manager::__private::search_upwards(&#name, #is_multiple)?;
+                if !found.is_empty() {
+                    for path in found {
+                        res.extend(file_options(path)?);
+                    }
+                    return ::std::result::Result::Ok(());
+                }
+                err_msg.push(::std::format!("file `{}` was not found in the parent directories", #name));
+            },
+        };
+
         config_paths_init.extend(quote_spanned! {span=>
             if let ::std::result::Result::Err(err) = (|| {
+                let file_options = #file_options;
                 let mut err_msg = ::std::vec![];
 
                 if let ::std::option::Option::<&::std::primitive::str>::Some(clap_long) = #clap_long {
-                    if let Some(field_match) = #matches.get_one::<::std::string::String>(clap_long) {
-                        res.push((#file_format, field_match.to_string()));
-                        return ::std::result::Result::Ok(());
-                    }
+                    #from_clap
                     err_msg.push(::std::format!("key `{}` was not found in command line", clap_long));
                 }
 
                 if let ::std::option::Option::<&::std::primitive::str>::Some(env_key) = #env_key {
-                    let from_env = #env_data.get(env_key);
-                    if let ::std::option::Option::Some(path) = from_env {
-                        res.push((#file_format, path.clone()));
-                        return ::std::result::Result::Ok(());
-                    }
+                    #from_env
                     err_msg.push(::std::format!("key `{}` was not found in environment", env_key));
                 }
 
-                if let ::std::option::Option::<&::std::primitive::str>::Some(default_path) = #default_path {
-                    res.push((#file_format, default_path.to_string()));
+                #discovered
+                #found_upwards
+
+                let default_paths: &[&::std::primitive::str] = #default_paths;
+                if !default_paths.is_empty() {
+                    res.extend(
+                        ::config_manager::__private::find_default_path(default_paths, #env_data, #is_optional)?
+                            .map(file_options)
+                            .transpose()?
+                            .flatten()
+                    );
                     return ::std::result::Result::Ok(());
                 }
                 err_msg.push("default path for file was not specified".into());
@@ -171,28 +413,14 @@ pub(super) fn gen_config_file_data(config_keys: Vec<ConfigFileInfo>) -> TokenStr
 
     quote! {
         {
-            (|| -> ::std::result::Result<::std::collections::HashMap::<::std::string::String, ::config_manager::__private::config::Value>, ::config_manager::Error> {
-                use ::config_manager::__private::config::Source;
-
-                let configs_paths: ::std::vec::Vec<(::config_manager::__private::config::FileFormat, ::std::string::String)> = {
+            (|| -> ::std::result::Result<::config_manager::__private::ConfigFileData, ::config_manager::Error> {
+                let configs_options: ::std::vec::Vec<::config_manager::FileOptions> = {
                     let mut res = ::std::vec![];
                     #config_paths_init
                     res
                 };
 
-                let mut res = ::std::collections::HashMap::new();
-                for (format, path) in configs_paths {
-                    res.extend(
-                        ::config_manager::__private::config::File::new(&path, format)
-                            .collect()
-                            .map_err(|err|
-                                ::config_manager::Error::ExternalError(
-                                    ::std::format!("failed to collect config file {}: {}", path, err)
-                                )
-                            )?
-                    );
-                }
-                ::std::result::Result::Ok(res)
+                ::config_manager::__private::collect_config_files(configs_options)
             })()
         }
     }
@@ -217,6 +445,8 @@ fn gen_sources() -> TokenStream {
     quote! {
         let (mut env_data, mut clap_data, mut config_file_data, mut env_prefix) =
             ::std::default::Default::default();
+        let mut custom_sources = ::std::vec::Vec::new();
+        let mut deny_unknown_keys = ::std::option::Option::None;
             for option in options {
                 match option {
                     ::config_manager::ConfigOption::EnvPrefix(pref) => {
@@ -246,25 +476,16 @@ fn gen_sources() -> TokenStream {
                     ::config_manager::ConfigOption::ExplicitSource(::config_manager::Source::ConfigFiles(
                         files,
                     )) => {
-                        config_file_data = ::std::option::Option::Some({
-                            let mut res = ::std::collections::HashMap::new();
-                            for ::config_manager::FileOptions { format, path } in files {
-                                res.extend(
-                                    ::config_manager::__private::config::Source::collect(
-                                        &::config_manager::__private::config::File::new(&path, format),
-                                    )
-                                    .map_err(|err| {
-                                        ::config_manager::Error::ExternalError(::std::format!(
-                                            "failed to collect config file {}: {}",
-                                            path,
-                                            err
-                                        ))
-                                    })?,
-                                );
-                            }
-                            res
-                        });
+                        config_file_data = ::std::option::Option::Some(
+                            ::config_manager::__private::collect_config_files(files)?
+                        );
                     }
+                    ::config_manager::ConfigOption::CustomSource(source) => custom_sources.push(source),
+                    ::config_manager::ConfigOption::DenyUnknownKeys(deny) => {
+                        deny_unknown_keys = ::std::option::Option::Some(deny);
+                    }
+                    // ConfigOption is non_exhaustive, the derive is released together with the crate
+                    _ => ::std::unreachable!("unsupported ConfigOption: {:?}", option),
                 }
             }
     }
@@ -292,14 +513,12 @@ pub(super) fn struct_initialization(
             ::std::option::Option::Some(data) => data,
             ::std::option::Option::None => #env_data?
         };
-        let config_file_data = &match config_file_data {
-            ::std::option::Option::Some(data) => data,
-            ::std::option::Option::None => #config_file_data?
-        };
         let env_prefix = match env_prefix {
             ::std::option::Option::Some(prefix) => ::std::option::Option::Some(prefix),
             ::std::option::Option::None => #env_prefix,
         };
+        #config_file_data
+        let custom_sources = &::config_manager::__private::CustomSources::from(custom_sources);
     };
 
     let fields_initialization =
@@ -11,6 +11,12 @@ pub(crate) struct InitializationInfo {
     pub(crate) configs: ConfigFilesInfo,
     pub(crate) clap_fields: Vec<ClapInitialization>,
     pub(crate) fields_json_definition: Vec<(proc_macro2::Ident, TokenStream)>,
+    pub(crate) config_keys: Vec<TokenStream>,
+    pub(crate) deny_unknown_keys: bool,
+    pub(crate) env_keys: Vec<TokenStream>,
+    pub(crate) unused_env: Option<bool>,
+    pub(crate) constraints: TokenStream,
+    pub(crate) validation: Option<Expr>,
     pub(crate) debug_cmd_input: Option<TokenStream>,
 }
 
@@ -25,16 +31,53 @@ pub(crate) fn generate_final_struct_and_supporting_code(
             ConfigFilesInfo {
                 configs_as_clap_args,
                 configs_attributes,
+                dotenv,
+                profiles,
             },
         clap_fields,
         fields_json_definition,
+        config_keys,
+        deny_unknown_keys,
+        mut env_keys,
+        unused_env,
+        constraints,
+        validation,
         debug_cmd_input,
     } = info;
 
     let clap_app = gen_clap_app(clap_app_info, configs_as_clap_args, clap_fields)?;
     let clap_data = gen_clap_matches(debug_cmd_input);
-    let config_file_data = gen_config_file_data(configs_attributes);
-    let env_data = gen_env_data();
+
+    // Environment variables with the paths of the files and the profile are consumed too
+    let config_env_keys = configs_attributes.iter().map(|config| &config.env_key);
+    env_keys.push(quote! {
+        ::std::vec::Vec::<::std::option::Option<&::std::primitive::str>>::from([#(#config_env_keys),*])
+            .into_iter()
+            .flatten()
+            .map(::std::string::ToString::to_string)
+            .collect()
+    });
+    let other_env_keys = dotenv
+        .iter()
+        .map(|dotenv| &dotenv.env_key)
+        .chain(profiles.iter().map(|profiles| &profiles.env_key));
+    env_keys.push(quote! {
+        ::std::vec::Vec::<::std::option::Option<::std::string::String>>::from([#(#other_env_keys),*])
+            .into_iter()
+            .flatten()
+            .collect()
+    });
+    let checks = [
+        gen_unknown_keys_check(deny_unknown_keys, &config_keys),
+        gen_unused_env_check(unused_env, &env_keys),
+    ];
+
+    let config_file_data = gen_config_file_data_binding(
+        gen_config_file_data(configs_attributes),
+        profiles.as_ref(),
+        quote!(#(#checks)*),
+    );
+    let env_data = gen_env_data(dotenv);
 
     let initialization = struct_initialization(
         &class_ident,
@@ -45,6 +88,7 @@ pub(crate) fn generate_final_struct_and_supporting_code(
         &option_to_tokens(&env_prefix),
         &config_file_data,
     );
+    let initialization = gen_struct_checks(initialization, constraints, validation.as_ref());
 
     Ok(quote! {
         impl ::config_manager::ConfigInit for #class_ident {
@@ -63,9 +107,17 @@ pub(crate) fn generate_flatten_implementation(
     class: Ident,
     clap_info: Punctuated<ClapInitialization, Token![.]>,
     fields_init: Vec<(Ident, TokenStream)>,
+    config_keys: Vec<TokenStream>,
+    env_keys: Vec<TokenStream>,
+    constraints: TokenStream,
+    validation: Option<Expr>,
 ) -> Result<TokenStream> {
     let get_args_impl = generate_get_args_impl(clap_info.into_iter())?;
-    let parse_impl = generate_parse_impl(fields_init, &class);
+    let parse_impl = gen_struct_checks(
+        generate_parse_impl(fields_init, &class),
+        constraints,
+        validation.as_ref(),
+    );
 
     Ok(quote! {
         impl ::config_manager::__private::Flatten for #class {
@@ -73,9 +125,23 @@ pub(crate) fn generate_flatten_implementation(
                 #get_args_impl
             }
 
+            fn config_keys() -> ::std::vec::Vec<::std::string::String> {
+                let keys: ::std::vec::Vec<::std::vec::Vec<::std::string::String>> = ::std::vec![#(#config_keys),*];
+                keys.concat()
+            }
+
+            #[allow(unused_variables)]
+            fn env_keys(
+                env_prefix: ::std::option::Option::<::std::string::String>,
+            ) -> ::std::result::Result<::std::vec::Vec<::std::string::String>, ::config_manager::Error> {
+                let keys: ::std::vec::Vec<::std::vec::Vec<::std::string::String>> = ::std::vec![#(#env_keys),*];
+                ::std::result::Result::Ok(keys.concat())
+            }
+
             fn parse(
                 env_data: &::config_manager::__private::EnvData,
-                config_file_data: &::std::collections::HashMap<::std::string::String, ::config_manager::__private::config::Value>,
+                config_file_data: &::config_manager::__private::ConfigFileData,
+                custom_sources: &::config_manager::__private::CustomSources,
                 clap_data: &::config_manager::__private::clap::ArgMatches,
                 env_prefix: ::std::option::Option::<::std::string::String>,
             ) -> Result<Self, ::config_manager::Error>
@@ -86,3 +152,35 @@ pub(crate) fn generate_flatten_implementation(
         }
     })
 }
+
+/// Checks the constraints between the fields of the initialized structure
+/// and passes it to the function of the `validate = ...` attribute.
+///
+/// Initialization of the fields fills the `supplied` map: names of the fields taken from the sources
+/// (not from the default) to the names of the sources.
+fn gen_struct_checks(
+    initialization: TokenStream,
+    constraints: TokenStream,
+    validation: Option<&Expr>,
+) -> TokenStream {
+    let validation = validation.map(|validation| {
+        let span = validation.span();
+        let validate = quote_spanned!(span=> (#validation)(&res));
+        quote! {
+            if let ::std::result::Result::Err(err) = #validate {
+                return ::std::result::Result::Err(::config_manager::Error::StructValidationFailed {
+                    path: ::std::string::String::new(),
+                    message: ::std::string::ToString::to_string(&err),
+                });
+            }
+        }
+    });
+    quote! {
+        #[allow(unused_mut, unused_variables)]
+        let mut supplied = ::std::collections::HashMap::<&::std::primitive::str, &::std::primitive::str>::new();
+        let res = { #initialization }?;
+        #constraints
+        #validation
+        ::std::result::Result::Ok(res)
+    }
+}
@@ -58,13 +58,22 @@ pub fn config(attrs: TokenStream0, input: TokenStream0) -> TokenStream0 {
         source,
         flatten,
         subcommand,
+        config_files,
+        config_profile,
         config,
         env_prefix,
         clap,
         global_name,
         file,
+        dotenv,
+        profiles,
         table,
         default_order,
+        secrets_dir,
+        interpolate,
+        lenient,
+        deny_unknown_keys,
+        unused_env,
         __debug_cmd_input__
     )
 )]
@@ -83,6 +92,12 @@ fn generate_config_inner(input: DeriveInput, crate_attrs: &[Meta]) -> Result<Tok
         debug_cmd_input,
         table_name,
         default_order,
+        secrets_dir,
+        interpolation,
+        lenient,
+        deny_unknown_keys,
+        unused_env,
+        validation,
     } = AppTopLevelInfo::extract(crate_attrs, docs)?;
 
     let class: DataStruct = match input.data {
@@ -92,6 +107,11 @@ fn generate_config_inner(input: DeriveInput, crate_attrs: &[Meta]) -> Result<Tok
 
     let mut fields_json_definition = Vec::new();
     let mut clap_fields = Vec::new();
+    let mut config_keys = Vec::new();
+    let mut env_keys = Vec::new();
+    let mut constraints = Vec::new();
+    let mut fields_with_sources = Vec::new();
+    let mut has_unused_env_field = false;
 
     for field in class.fields {
         check_field_attributes(&field)?;
@@ -100,13 +120,34 @@ fn generate_config_inner(input: DeriveInput, crate_attrs: &[Meta]) -> Result<Tok
             process_flatten_field(field)?
         } else if field_is_subcommand(&field).is_some() {
             process_subcommand_field(field, &debug_cmd_input)?
+        } else if field_is_config_files(&field) {
+            process_config_files_field(field)
+        } else if field_is_config_profile(&field) {
+            process_config_profile_field(field)
+        } else if field_is_unused_env(&field) {
+            has_unused_env_field = true;
+            process_unused_env_field(field)
         } else {
-            process_field(field, &table_name, &default_order)?
+            fields_with_sources.push(field.ident.clone().unwrap().to_string());
+            process_field(
+                field,
+                &table_name,
+                &default_order,
+                &secrets_dir,
+                &interpolation,
+                lenient,
+            )?
         };
 
         fields_json_definition.push((res.name, res.initialization));
         clap_fields.push(res.clap_field);
+        config_keys.push(res.config_keys);
+        env_keys.push(res.env_keys);
+        constraints.extend(res.constraints);
     }
+    let constraints = gen_constraints_checks(constraints, &fields_with_sources)?;
+    // The field collects the unused variables even if the attribute is not set
+    let unused_env = unused_env.or(has_unused_env_field.then_some(false));
 
     generate_final_struct_and_supporting_code(InitializationInfo {
         env_prefix,
@@ -115,13 +156,34 @@ fn generate_config_inner(input: DeriveInput, crate_attrs: &[Meta]) -> Result<Tok
         configs,
         clap_fields,
         fields_json_definition,
+        config_keys,
+        deny_unknown_keys,
+        env_keys,
+        unused_env,
+        constraints,
+        validation,
         debug_cmd_input,
     })
 }
 
 /// Annotated with this macro structure can be used
 /// as a flatten argument in the [config](attr.config.html) macro.
-#[proc_macro_derive(Flatten, attributes(source, flatten, subcommand, table, default_order))]
+#[proc_macro_derive(
+    Flatten,
+    attributes(
+        source,
+        flatten,
+        subcommand,
+        config_files,
+        config_profile,
+        table,
+        default_order,
+        secrets_dir,
+        interpolate,
+        lenient,
+        validate
+    )
+)]
 pub fn generate_flatten(input: TokenStream0) -> TokenStream0 {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -140,6 +202,10 @@ fn generate_flatten_inner(input: DeriveInput) -> Result<TokenStream> {
     check_unfamilliar_attrs(&class_attrs, ALLOWED_FLATTEN_ATTRS)?;
     let table_name = extract_table_name(&class_attrs)?;
     let default_order = extract_source_order(&class_attrs)?;
+    let secrets_dir = extract_secrets_dir(&class_attrs)?;
+    let interpolation = extract_interpolation(&class_attrs)?;
+    let lenient = extract_lenient(&class_attrs)?;
+    let validation = extract_struct_validation(&class_attrs)?;
 
     let class_ident = input.ident;
     let class: DataStruct = match input.data {
@@ -149,6 +215,10 @@ fn generate_flatten_inner(input: DeriveInput) -> Result<TokenStream> {
 
     let mut fields_json_definition = Vec::new();
     let mut clap_fields = Punctuated::<ClapInitialization, Token![.]>::new();
+    let mut config_keys = Vec::new();
+    let mut env_keys = Vec::new();
+    let mut constraints = Vec::new();
+    let mut fields_with_sources = Vec::new();
 
     for field in class.fields {
         check_field_attributes(&field)?;
@@ -160,13 +230,37 @@ fn generate_flatten_inner(input: DeriveInput) -> Result<TokenStream> {
                 attr.meta.span(),
                 "subcommands are forbidden in the nested structures",
             ))
+        } else if field_is_config_files(&field) {
+            Ok(process_config_files_field(field))
+        } else if field_is_config_profile(&field) {
+            Ok(process_config_profile_field(field))
         } else {
-            process_field(field, &table_name, &default_order)
+            fields_with_sources.push(field.ident.clone().unwrap().to_string());
+            process_field(
+                field,
+                &table_name,
+                &default_order,
+                &secrets_dir,
+                &interpolation,
+                lenient,
+            )
         }?;
 
         fields_json_definition.push((res.name, res.initialization));
         clap_fields.push(res.clap_field);
+        config_keys.push(res.config_keys);
+        env_keys.push(res.env_keys);
+        constraints.extend(res.constraints);
     }
+    let constraints = gen_constraints_checks(constraints, &fields_with_sources)?;
 
-    generate_flatten_implementation(class_ident, clap_fields, fields_json_definition)
+    generate_flatten_implementation(
+        class_ident,
+        clap_fields,
+        fields_json_definition,
+        config_keys,
+        env_keys,
+        constraints,
+        validation,
+    )
 }
@@ -1,7 +1,6 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 JSRPC “Kryptonite”
 
-use strum_macros::EnumIter;
 use syn::{Attribute, Meta};
 
 use super::meta_value_lit;
@@ -9,28 +8,62 @@ use super::meta_value_lit;
 pub(crate) const CLAP_KEY: &str = "clap";
 pub(crate) const ENV_KEY: &str = "env";
 pub(crate) const CONFIG_KEY: &str = "config";
+pub(crate) const ENV_FILE_KEY: &str = "env_file";
+pub(crate) const SECRET_FILE_KEY: &str = "secret_file";
+pub(crate) const SECRETS_DIR_KEY: &str = "secrets_dir";
+pub(crate) const SECRET_KEY: &str = "secret";
+pub(crate) const EXEC_KEY: &str = "exec";
+pub(crate) const CUSTOM_KEY: &str = "custom";
 pub(crate) const DOCS_KEY: &str = "doc";
 pub(crate) const DESERIALIZER: &str = "deserialize_with";
 pub(crate) const DEFAULT: &str = "default";
+pub(crate) const RELATIVE_TO_FILE: &str = "relative_to_file";
+pub(crate) const INTERPOLATE_KEY: &str = "interpolate";
+pub(crate) const LENIENT_KEY: &str = "lenient";
+pub(crate) const VALIDATE_KEY: &str = "validate";
+pub(crate) const REQUIRED_IF_KEY: &str = "required_if";
+pub(crate) const CONFLICTS_WITH_KEY: &str = "conflicts_with";
 pub(crate) const ENV_PREFIX_KEY: &str = "env_prefix";
 pub(crate) const SOURCE_KEY: &str = "source";
 pub(crate) const CONFIG_FILE_KEY: &str = "file";
+pub(crate) const DOTENV_KEY: &str = "dotenv";
+pub(crate) const PROFILES_KEY: &str = "profiles";
+pub(crate) const DENY_UNKNOWN_KEYS_KEY: &str = "deny_unknown_keys";
+pub(crate) const UNUSED_ENV_KEY: &str = "unused_env";
 pub(crate) const DEBUG_INPUT_KEY: &str = "__debug_cmd_input__";
 pub(crate) const TABLE_NAME_KEY: &str = "table";
 pub(crate) const SOURCE_ORDER_KEY: &str = "default_order";
 pub(crate) const FLATTEN: &str = "flatten";
 pub(crate) const SUBCOMMAND: &str = "subcommand";
+pub(crate) const CONFIG_FILES: &str = "config_files";
+pub(crate) const CONFIG_PROFILE: &str = "config_profile";
 
 pub(crate) const ALLOWED_CRATE_ATTRS: &[&str] = &[
     ENV_PREFIX_KEY,
     CONFIG_FILE_KEY,
+    DOTENV_KEY,
+    PROFILES_KEY,
     DEBUG_INPUT_KEY,
     TABLE_NAME_KEY,
     SOURCE_ORDER_KEY,
+    SECRETS_DIR_KEY,
+    INTERPOLATE_KEY,
+    LENIENT_KEY,
+    DENY_UNKNOWN_KEYS_KEY,
+    UNUSED_ENV_KEY,
+    VALIDATE_KEY,
     CLAP_KEY,
     DOCS_KEY,
 ];
-pub(crate) const ALLOWED_FLATTEN_ATTRS: &[&str] = &[TABLE_NAME_KEY, SOURCE_ORDER_KEY, DOCS_KEY];
+pub(crate) const ALLOWED_FLATTEN_ATTRS: &[&str] = &[
+    TABLE_NAME_KEY,
+    SOURCE_ORDER_KEY,
+    SECRETS_DIR_KEY,
+    INTERPOLATE_KEY,
+    LENIENT_KEY,
+    VALIDATE_KEY,
+    DOCS_KEY,
+];
 pub(crate) const ALLOWED_CLAP_APP_ATTRS: &[&str] = &[
     "name",
     "version",
@@ -69,6 +102,8 @@ pub(crate) const ALLOWED_CLAP_FIELD_ATTRS: &[&str] = &[
     "short",
     "long",
     "flag",
+    "negatable",
+    "count",
     "help_heading",
     "alias",
     "short_alias",
@@ -113,6 +148,8 @@ pub(crate) const CLAP_FLAG_ATTRIBUTES: &[&str] = &[
     "arg_required_else_help",
     // Arg
     "flag",
+    "negatable",
+    "count",
     "last",
     "exclusive",
     "ignore_case",
@@ -152,52 +189,6 @@ pub(crate) const CLAP_ATTRIBUTE_TAKES_INT: &[&str] = &[
 pub(crate) const CLAP_ATTRIBUTE_TAKES_CODE: &[&str] = &["color", "styles", "value_hint"];
 // todo: INDEX, aliases, short_aliases
 
-#[derive(EnumIter, strum_macros::Display)]
-pub(super) enum TopLevelAttr {
-    #[strum(serialize = "default")]
-    Default,
-    #[strum(serialize = "env")]
-    Env,
-    #[strum(serialize = "config")]
-    Config,
-    #[strum(serialize = "deserialize_with")]
-    Deserializer,
-}
-
-#[derive(EnumIter, strum_macros::Display, Copy, Clone)]
-pub(super) enum ClapNestedAttr {
-    #[strum(serialize = "short")]
-    Short,
-    #[strum(serialize = "long")]
-    Long,
-    #[strum(serialize = "help")]
-    Help,
-}
-
-#[derive(EnumIter, strum_macros::Display)]
-pub(super) enum ClapAppAttr {
-    #[strum(serialize = "author")]
-    Author,
-    #[strum(serialize = "about")]
-    About,
-    #[strum(serialize = "version")]
-    Version,
-    #[strum(serialize = "override_help")]
-    Help,
-}
-
-#[derive(EnumIter, strum_macros::Display)]
-pub(super) enum ConfigFileAttr {
-    #[strum(serialize = "format")]
-    Format,
-    #[strum(serialize = "env")]
-    EnvKey,
-    #[strum(serialize = "optional")]
-    Optional,
-    #[strum(serialize = "default")]
-    Default,
-}
-
 pub(crate) fn extract_docs(attrs: &[Attribute]) -> Option<String> {
     let mut res = String::new();
     for attr in attrs {
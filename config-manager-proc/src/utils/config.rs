@@ -3,7 +3,7 @@
 
 use std::collections::HashSet;
 
-use super::attributes::*;
+use super::{attributes::*, option_to_tokens};
 use crate::*;
 
 fn str_to_config_format_repr<S: AsRef<str>>(s: S, span: Span) -> Result<TokenStream> {
@@ -26,11 +26,14 @@ fn str_to_config_format_repr<S: AsRef<str>>(s: S, span: Span) -> Result<TokenStr
 
 struct ParsedConfigFileAttributes {
     span: Span,
-    file_format: TokenStream,
+    kind: ConfigFileKind,
     clap_info: Option<NormalClapFieldInfo>,
     env_key: Option<TokenStream>,
     optional: bool,
-    default: Option<TokenStream>,
+    multiple: bool,
+    discover: Option<Option<TokenStream>>,
+    search_upwards: Option<TokenStream>,
+    default: Vec<TokenStream>,
 }
 
 fn handle_file_attributes(class_attributes: &[Meta]) -> Result<Vec<ParsedConfigFileAttributes>> {
@@ -51,7 +54,12 @@ fn handle_file_attribute(attr: &Meta) -> Result<ParsedConfigFileAttributes> {
     let mut file_format = None;
     let mut env_key = None;
     let mut optional = false;
-    let mut default = None;
+    let mut multiple = false;
+    let mut directory = false;
+    let mut glob = None;
+    let mut discover = None;
+    let mut search_upwards = None;
+    let mut default = vec![];
 
     for arg in nested {
         match path_to_string(arg.path()).as_str() {
@@ -85,15 +93,60 @@ fn handle_file_attribute(attr: &Meta) -> Result<ParsedConfigFileAttributes> {
                 }
                 optional = true;
             }
-            "default" => default = set_config_attr(default.is_some(), &arg, "default")?,
+            "multiple" => {
+                if multiple {
+                    panic_span!(arg.span(), "attempted to set multiple twice")
+                }
+                if !matches!(arg, Meta::Path(_)) {
+                    panic_span!(
+                        arg.span(),
+                        "multiple cannot take values. Usage: file(multiple)"
+                    )
+                }
+                multiple = true;
+            }
+            "dir" => {
+                if directory {
+                    panic_span!(arg.span(), "attempted to set dir twice")
+                }
+                if !matches!(arg, Meta::Path(_)) {
+                    panic_span!(
+                        arg.span(),
+                        "dir cannot take values, path to the directory is set by clap, env or default. Usage: file(dir)"
+                    )
+                }
+                directory = true;
+            }
+            "glob" => glob = set_config_attr(glob.is_some(), &arg, "glob")?,
+            "discover" => {
+                if discover.is_some() {
+                    panic_span!(arg.span(), "attempted to set discover twice")
+                }
+                discover = Some(meta_to_option(&arg)?);
+            }
+            "search_upwards" => {
+                search_upwards = set_config_attr(search_upwards.is_some(), &arg, "search_upwards")?
+            }
+            "default" => default = set_default_paths(!default.is_empty(), &arg)?,
             other => panic_span!(arg.span(), "unknown attribute {other}"),
         }
     }
 
-    if clap_info.is_none() && env_key.is_none() && default.is_none() {
+    if clap_info.is_none()
+        && env_key.is_none()
+        && discover.is_none()
+        && search_upwards.is_none()
+        && default.is_empty()
+    {
         panic_span!(
             attr.span(),
-            "you must specify at least one of (clap, env, default)"
+            "you must specify at least one of (clap, env, discover, search_upwards, default)"
+        );
+    }
+    if discover.is_some() && search_upwards.is_some() {
+        panic_span!(
+            attr.span(),
+            "`discover` and `search_upwards` can't be used together"
         );
     }
     if let Some(clap_info) = &clap_info {
@@ -108,19 +161,85 @@ fn handle_file_attribute(attr: &Meta) -> Result<ParsedConfigFileAttributes> {
 
     let clap_info = clap_info
         .map(|info| info.normalize(Default::default()))
-        .transpose()?;
+        .transpose()?
+        .map(|mut info| {
+            if multiple {
+                info.attributes.insert(
+                    "action".to_string(),
+                    quote_spanned!(info.span=> clap::ArgAction::Append),
+                );
+            }
+            info
+        });
+
+    let kind = if directory {
+        if discover.is_some() || search_upwards.is_some() {
+            panic_span!(
+                attr.span(),
+                "`discover` and `search_upwards` can't be used with `dir`: only files are looked for"
+            );
+        }
+        if file_format.is_some() {
+            panic_span!(
+                attr.span(),
+                "`format` can't be set for the directory: formats of the files are detected by their extensions"
+            );
+        }
+        let glob = match glob {
+            None => quote_spanned!(attr.span()=> ::std::option::Option::None),
+            Some(glob) => {
+                quote_spanned!(attr.span()=> ::std::option::Option::Some(#glob.to_string()))
+            }
+        };
+        ConfigFileKind::Directory { glob }
+    } else {
+        if glob.is_some() {
+            panic_span!(attr.span(), "`glob` can be set only along with `dir`");
+        }
+        if file_format.is_none() && discover.is_none() && search_upwards.is_none() {
+            panic_span!(attr.span(), "`format` attribute of config file must be set");
+        }
+        ConfigFileKind::File {
+            format: file_format,
+        }
+    };
 
     Ok(ParsedConfigFileAttributes {
         span: attr.span(),
         default,
         optional,
+        multiple,
+        discover,
+        search_upwards,
         clap_info,
         env_key,
-        file_format: file_format
-            .err_on_none(attr.span(), "`format` attribute of config file must be set")?,
+        kind,
     })
 }
 
+fn set_default_paths(already_set: bool, arg: &Meta) -> Result<Vec<TokenStream>> {
+    let paths = match arg {
+        Meta::NameValue(MetaNameValue {
+            value: Expr::Array(paths),
+            ..
+        }) => {
+            if already_set {
+                panic_span!(arg.span(), "attempted to set default twice")
+            }
+            paths
+                .elems
+                .iter()
+                .map(ToTokens::to_token_stream)
+                .collect::<Vec<_>>()
+        }
+        _ => vec![set_config_attr(already_set, arg, "default")?.unwrap()],
+    };
+    if paths.is_empty() {
+        panic_span!(arg.span(), "file(default) can't be empty");
+    }
+    Ok(paths)
+}
+
 fn set_config_attr<N: AsRef<str>>(
     already_set: bool,
     arg: &Meta,
@@ -138,16 +257,41 @@ fn set_config_attr<N: AsRef<str>>(
 
 pub(crate) struct ConfigFilesInfo {
     pub(crate) configs_attributes: Vec<ConfigFileInfo>,
+    pub(crate) dotenv: Option<DotenvInfo>,
+    pub(crate) profiles: Option<ProfilesInfo>,
     pub(crate) configs_as_clap_args: Punctuated<ClapInitialization, Token![.]>,
 }
 
+pub(crate) struct DotenvInfo {
+    pub(crate) span: Span,
+    pub(crate) clap_long: TokenStream,
+    pub(crate) env_key: TokenStream,
+    pub(crate) default_path: TokenStream,
+    pub(crate) is_optional: bool,
+}
+
+pub(crate) struct ProfilesInfo {
+    pub(crate) span: Span,
+    pub(crate) clap_long: TokenStream,
+    pub(crate) env_key: TokenStream,
+    pub(crate) default: TokenStream,
+}
+
+pub(crate) enum ConfigFileKind {
+    File { format: Option<TokenStream> },
+    Directory { glob: TokenStream },
+}
+
 pub(crate) struct ConfigFileInfo {
     pub(crate) span: Span,
-    pub(crate) file_format: TokenStream,
+    pub(crate) kind: ConfigFileKind,
     pub(crate) clap_long: TokenStream,
     pub(crate) env_key: TokenStream,
     pub(crate) is_optional: bool,
-    pub(crate) default_path: TokenStream,
+    pub(crate) is_multiple: bool,
+    pub(crate) discover: Option<Option<TokenStream>>,
+    pub(crate) search_upwards: Option<TokenStream>,
+    pub(crate) default_paths: TokenStream,
 }
 
 pub(crate) fn extract_configs_info(class_attributes: &[Meta]) -> Result<ConfigFilesInfo> {
@@ -158,21 +302,16 @@ pub(crate) fn extract_configs_info(class_attributes: &[Meta]) -> Result<ConfigFi
 
     for ParsedConfigFileAttributes {
         span,
-        file_format,
+        kind,
         clap_info,
         env_key,
         optional,
+        multiple,
+        discover,
+        search_upwards,
         default,
     } in handle_file_attributes(class_attributes)?
     {
-        if optional && default.is_some() {
-            panic_span!(
-                span,
-                "setting optional to true while specifying the default path is pointless, \
-                    since the config file will always be tried to parse"
-            );
-        }
-
         let clap_long = if let Some(clap_info) = &clap_info {
             let clap_long = clap_info.long.clone();
             let is_new = config_clap_keys.insert(clap_long.to_string());
@@ -199,19 +338,18 @@ pub(crate) fn extract_configs_info(class_attributes: &[Meta]) -> Result<ConfigFi
             quote_spanned!(span=> ::std::option::Option::<&::std::primitive::str>::None)
         };
 
-        let default_path = if let Some(default) = default {
-            quote_spanned!(span=> ::std::option::Option::Some(#default))
-        } else {
-            quote_spanned!(span=> ::std::option::Option::None)
-        };
+        let default_paths = quote_spanned!(span=> &[#(#default),*]);
 
         configs_attributes.push(ConfigFileInfo {
             span,
-            file_format,
+            kind,
             clap_long,
             env_key,
             is_optional: optional,
-            default_path,
+            is_multiple: multiple,
+            discover,
+            search_upwards,
+            default_paths,
         });
 
         if let Some(clap_info) = clap_info {
@@ -219,8 +357,191 @@ pub(crate) fn extract_configs_info(class_attributes: &[Meta]) -> Result<ConfigFi
         }
     }
 
+    let mut register_clap_arg = |span: Span, clap_info: Option<NormalClapFieldInfo>| {
+        Ok(match clap_info {
+            None => {
+                quote_spanned!(span=> ::std::option::Option::<&::std::primitive::str>::None)
+            }
+            Some(clap_info) => {
+                let clap_long = clap_info.long.clone();
+                if !config_clap_keys.insert(clap_long.to_string()) {
+                    panic_span!(
+                        span,
+                        "clap key {clap_long} is already specified for config file"
+                    );
+                }
+                configs_as_clap_args.push(ClapInitialization::Normal(clap_info));
+                quote_spanned!(span=> ::std::option::Option::Some(#clap_long))
+            }
+        })
+    };
+
+    let dotenv = match handle_dotenv_attribute(class_attributes)? {
+        None => None,
+        Some(ParsedValueSources {
+            span,
+            clap_info,
+            env_key,
+            optional,
+            default,
+        }) => Some(DotenvInfo {
+            span,
+            clap_long: register_clap_arg(span, clap_info)?,
+            env_key: option_to_tokens(&env_key),
+            default_path: option_to_tokens(&default),
+            is_optional: optional,
+        }),
+    };
+
+    let profiles = match handle_profiles_attribute(class_attributes)? {
+        None => None,
+        Some(ParsedValueSources {
+            span,
+            clap_info,
+            env_key,
+            default,
+            ..
+        }) => Some(ProfilesInfo {
+            span,
+            clap_long: register_clap_arg(span, clap_info)?,
+            env_key: option_to_tokens(&env_key),
+            default: option_to_tokens(&default),
+        }),
+    };
+
     Ok(ConfigFilesInfo {
         configs_attributes,
+        dotenv,
+        profiles,
         configs_as_clap_args,
     })
 }
+
+/// Value that can be set by `clap`, `env` or `default`, like the path to the dotenv file.
+struct ParsedValueSources {
+    span: Span,
+    clap_info: Option<NormalClapFieldInfo>,
+    env_key: Option<TokenStream>,
+    optional: bool,
+    default: Option<TokenStream>,
+}
+
+fn handle_dotenv_attribute(class_attributes: &[Meta]) -> Result<Option<ParsedValueSources>> {
+    let attr = match find_single_attribute(class_attributes, DOTENV_KEY)? {
+        None => return Ok(None),
+        Some(attr) => attr,
+    };
+
+    let nested = match attr {
+        // `dotenv = ".env"` is a shorthand for `dotenv(default = ".env", optional)`
+        Meta::NameValue(_) => {
+            return Ok(Some(ParsedValueSources {
+                span: attr.span(),
+                clap_info: None,
+                env_key: None,
+                optional: true,
+                default: set_config_attr(false, attr, "dotenv")?,
+            }))
+        }
+        Meta::List(list) => {
+            list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?
+        }
+        Meta::Path(_) => panic_span!(
+            attr.span(),
+            "dotenv attribute must match \"dotenv = \"...\"\" or \"dotenv(...)\""
+        ),
+    };
+
+    parse_value_sources(attr, DOTENV_KEY, nested, true).map(Some)
+}
+
+fn handle_profiles_attribute(class_attributes: &[Meta]) -> Result<Option<ParsedValueSources>> {
+    let attr = match find_single_attribute(class_attributes, PROFILES_KEY)? {
+        None => return Ok(None),
+        Some(attr) => attr,
+    };
+
+    let nested = attr
+        .require_list()
+        .map_err(|_| {
+            Error::new(
+                attr.span(),
+                "profiles attribute must match \"profiles(...)\"",
+            )
+        })?
+        .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+    parse_value_sources(attr, PROFILES_KEY, nested, false).map(Some)
+}
+
+fn find_single_attribute<'a>(class_attributes: &'a [Meta], key: &str) -> Result<Option<&'a Meta>> {
+    let mut attrs = class_attributes.iter().filter(|m| m.path().is_ident(key));
+    let attr = attrs.next();
+    if let Some(second) = attrs.next() {
+        panic_span!(second.span(), "{key} can be set only once");
+    }
+    Ok(attr)
+}
+
+fn parse_value_sources(
+    attr: &Meta,
+    attr_name: &str,
+    nested: Punctuated<Meta, Token![,]>,
+    allow_optional: bool,
+) -> Result<ParsedValueSources> {
+    let mut clap_info = None;
+    let mut env_key = None;
+    let mut optional = false;
+    let mut default = None;
+    for arg in nested {
+        match path_to_string(arg.path()).as_str() {
+            "clap" => {
+                if clap_info.is_some() {
+                    panic_span!(arg.span(), "attempted to set clap twice")
+                }
+                let clap_list = arg.require_list().map_err(|_err| {
+                    Error::new(arg.span(), "clap attribute must match \"clap(...)\"")
+                })?;
+                let info = parse_clap_field_attribute(clap_list, false)?;
+                if !info.has_explicit_long() {
+                    panic_span!(
+                        info.span,
+                        "if #[clap] attribute is specified for {attr_name}, nested `long = ...` \
+                            must be provided. Otherwise it's impossible to determine arg name"
+                    );
+                }
+                clap_info = Some(info.normalize(Default::default())?);
+            }
+            "env" => env_key = set_config_attr(env_key.is_some(), &arg, "env")?,
+            "optional" if allow_optional => {
+                if optional {
+                    panic_span!(arg.span(), "attempted to set optional twice")
+                }
+                if !matches!(arg, Meta::Path(_)) {
+                    panic_span!(
+                        arg.span(),
+                        "optional cannot take values. Usage: {attr_name}(optional)"
+                    )
+                }
+                optional = true;
+            }
+            "default" => default = set_config_attr(default.is_some(), &arg, "default")?,
+            other => panic_span!(arg.span(), "unknown attribute {other}"),
+        }
+    }
+
+    if clap_info.is_none() && env_key.is_none() && default.is_none() {
+        panic_span!(
+            attr.span(),
+            "you must specify at least one of (clap, env, default)"
+        );
+    }
+
+    Ok(ParsedValueSources {
+        span: attr.span(),
+        clap_info,
+        env_key,
+        optional,
+        default,
+    })
+}
@@ -29,16 +29,25 @@ pub(crate) struct ProcessFieldResult {
     pub(crate) name: proc_macro2::Ident,
     pub(crate) clap_field: ClapInitialization,
     pub(crate) initialization: TokenStream,
+    /// Expression of the `Vec<String>` type: full paths of the configuration file keys the field consumes.
+    pub(crate) config_keys: TokenStream,
+    /// Expression of the `Vec<String>` type: names of the environment variables the field consumes.
+    pub(crate) env_keys: TokenStream,
+    /// Constraints between the field and the other fields of the structure.
+    pub(crate) constraints: Vec<FieldConstraint>,
 }
 
 pub(crate) fn process_field(
     field: Field,
     table_name: &Option<TokenStream>,
     default_order: &Option<ExtractedAttributes>,
+    secrets_dir: &Option<TokenStream>,
+    interpolation: &Option<Interpolation>,
+    lenient: bool,
 ) -> Result<ProcessFieldResult> {
     let field_name = field.ident.clone().unwrap();
 
-    let attributes_order = extract_attributes(&field, table_name)?
+    let mut attributes_order = extract_attributes(&field, table_name, secrets_dir)?
         .or_else(|| default_order.clone())
         .unwrap_or_else(|| ExtractedAttributes {
             variables: vec![
@@ -49,11 +58,27 @@ pub(crate) fn process_field(
             span: field.span(),
             default: None,
             deserializer: None,
+            interpolation: None,
+            lenient: None,
+            validations: vec![],
+            constraints: vec![],
+            secret: false,
         });
+    // Field-level interpolation overrides the struct-level one
+    if attributes_order.interpolation.is_none() {
+        attributes_order.interpolation = interpolation.clone();
+    }
+    // Struct-level lenient parsing affects only boolean and integer fields
+    if attributes_order.lenient.is_none() && lenient {
+        attributes_order.lenient = Lenient::of(&field.ty);
+    }
 
     Ok(ProcessFieldResult {
         initialization: attributes_order.gen_init(&field),
-        clap_field: match attributes_order.clap_field(&field_name.to_string())? {
+        config_keys: attributes_order.config_keys(&field_name.to_string()),
+        env_keys: attributes_order.env_keys(&field_name.to_string()),
+        constraints: attributes_order.constraints.clone(),
+        clap_field: match attributes_order.clap_field(&field_name.to_string(), &field.ty)? {
             Some(init) => ClapInitialization::Normal(init),
             None => ClapInitialization::None,
         },
@@ -68,13 +93,22 @@ pub(crate) fn field_is_flatten(field: &Field) -> bool {
 pub(crate) fn process_flatten_field(field: Field) -> Result<ProcessFieldResult> {
     let span = field.span();
     let name = field.ident.clone().unwrap();
+    let string_name = name.to_string();
     let ty = field.ty;
 
     Ok(ProcessFieldResult {
         name,
         clap_field: ClapInitialization::Flatten(ty.clone()),
+        config_keys: quote_spanned! {span=>
+            <#ty as ::config_manager::__private::Flatten>::config_keys()
+        },
+        env_keys: quote_spanned! {span=>
+            <#ty as ::config_manager::__private::Flatten>::env_keys(env_prefix.clone())?
+        },
+        constraints: vec![],
         initialization: quote_spanned! {span=>
-            <#ty as ::config_manager::__private::Flatten>::parse(env_data, config_file_data, clap_data, env_prefix.clone())?
+            <#ty as ::config_manager::__private::Flatten>::parse(env_data, config_file_data, custom_sources, clap_data, env_prefix.clone())
+                .map_err(|err| ::config_manager::__private::nest_error(err, #string_name))?
         },
     })
 }
@@ -124,17 +158,93 @@ pub(crate) fn process_subcommand_field(
         name,
         clap_field: ClapInitialization::Subcommand(ty),
         initialization,
+        config_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        env_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        constraints: vec![],
     })
 }
 
+pub(crate) fn field_is_config_files(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident(CONFIG_FILES))
+}
+
+pub(crate) fn process_config_files_field(field: Field) -> ProcessFieldResult {
+    let span = field.span();
+
+    ProcessFieldResult {
+        name: field.ident.unwrap(),
+        clap_field: ClapInitialization::None,
+        initialization: quote_spanned! {span=>
+            ::std::iter::FromIterator::from_iter(config_file_data.files().iter().cloned())
+        },
+        config_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        env_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        constraints: vec![],
+    }
+}
+
+pub(crate) fn field_is_config_profile(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident(CONFIG_PROFILE))
+}
+
+pub(crate) fn process_config_profile_field(field: Field) -> ProcessFieldResult {
+    let span = field.span();
+
+    ProcessFieldResult {
+        name: field.ident.unwrap(),
+        clap_field: ClapInitialization::None,
+        initialization: quote_spanned! {span=>
+            config_file_data.profile().map(::std::convert::Into::into)
+        },
+        config_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        env_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        constraints: vec![],
+    }
+}
+
+pub(crate) fn field_is_unused_env(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident(UNUSED_ENV_KEY))
+}
+
+pub(crate) fn process_unused_env_field(field: Field) -> ProcessFieldResult {
+    let span = field.span();
+
+    ProcessFieldResult {
+        name: field.ident.unwrap(),
+        clap_field: ClapInitialization::None,
+        initialization: quote_spanned! {span=>
+            ::std::iter::FromIterator::from_iter(unused_env.iter().cloned())
+        },
+        config_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        env_keys: quote_spanned!(span=> ::std::vec::Vec::new()),
+        constraints: vec![],
+    }
+}
+
 pub(crate) fn check_field_attributes(field: &Field) -> Result<()> {
     let applied_crate_attrs = field
         .attrs
         .iter()
         .filter_map(|attr| {
-            [SOURCE_KEY, FLATTEN, SUBCOMMAND]
-                .into_iter()
-                .find(|crate_attr| crate_attr == &path_to_string(attr.path()))
+            [
+                SOURCE_KEY,
+                FLATTEN,
+                SUBCOMMAND,
+                CONFIG_FILES,
+                CONFIG_PROFILE,
+                UNUSED_ENV_KEY,
+            ]
+            .into_iter()
+            .find(|crate_attr| crate_attr == &path_to_string(attr.path()))
         })
         .collect::<Vec<_>>();
 
@@ -146,3 +256,23 @@ pub(crate) fn check_field_attributes(field: &Field) -> Result<()> {
         Ok(())
     }
 }
+
+/// Checks of the constraints between the fields.
+/// Only the fields with the sources (`fields`) can be referenced by the constraints.
+pub(crate) fn gen_constraints_checks(
+    constraints: Vec<FieldConstraint>,
+    fields: &[String],
+) -> Result<TokenStream> {
+    let mut checks = TokenStream::new();
+    for constraint in constraints {
+        let other = constraint.other();
+        if !fields.contains(&other.to_string()) {
+            panic_span!(
+                other.span(),
+                "constraint must reference a field of the structure that is not flatten, subcommand, config_files, config_profile or unused_env"
+            )
+        }
+        checks.extend(constraint.gen_check());
+    }
+    Ok(checks)
+}
@@ -4,6 +4,7 @@
 use std::fmt::Display;
 
 use super::*;
+use crate::utils::meta_value_lit;
 
 impl ToTokens for ClapInitialization {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -19,9 +20,14 @@ impl ToTokens for ClapInitialization {
                     <#tp as ::config_manager::__private::clap::Subcommand>::augment_subcommands(app)
                 }
             }
-            Self::Normal(info) => quote_spanned! {info.span()=>
-                 arg(#info)
-            },
+            Self::Normal(info) => {
+                let negation = info
+                    .negation()
+                    .map(|neg| quote_spanned!(info.span=> .arg(#neg)));
+                quote_spanned! {info.span()=>
+                     arg(#info)#negation
+                }
+            }
         })
     }
 }
@@ -36,8 +42,11 @@ impl ToTokens for NormalClapFieldInfo {
                 clap::Arg::new(#name).long(#name).required(false)
             };
 
+            attrs.remove("negatable");
             field.extend(if attrs.remove("flag").is_some() {
                 quote_spanned!(span=> .num_args(0..=1).default_missing_value("true"))
+            } else if attrs.remove("count").is_some() {
+                quote_spanned!(span=> .action(clap::ArgAction::Count))
             } else {
                 quote_spanned!(span=> .num_args(1))
             });
@@ -52,12 +61,40 @@ impl ToTokens for NormalClapFieldInfo {
     }
 }
 
+impl NormalClapFieldInfo {
+    /// `--no-<long>` argument of the negatable flag: it sets the field to `false`
+    /// and overrides the flag itself (the last one wins).
+    pub(crate) fn negation(&self) -> Option<TokenStream> {
+        if !self.attributes.contains_key("negatable") {
+            return None;
+        }
+        let span = self.span;
+        let long = &self.long;
+        let name = negated_long(long, span);
+        let help = format!("Sets --{} to false", long.to_string().trim_matches('"'));
+        Some(quote_spanned! {span=>
+            clap::Arg::new(#name)
+                .long(#name)
+                .required(false)
+                .action(clap::ArgAction::SetTrue)
+                .overrides_with(#long)
+                .help(#help)
+        })
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct ExtractedAttributes {
     pub(crate) span: Span,
     pub(crate) variables: Vec<FieldAttribute>,
     pub(crate) default: Option<Default>,
     pub(crate) deserializer: Option<(TokenStream, Span)>,
+    pub(crate) interpolation: Option<Interpolation>,
+    pub(crate) lenient: Option<Lenient>,
+    pub(crate) validations: Vec<Validation>,
+    pub(crate) constraints: Vec<FieldConstraint>,
+    /// Value of the field is masked in the error messages.
+    pub(crate) secret: bool,
 }
 
 impl ExtractedAttributes {
@@ -67,6 +104,11 @@ impl ExtractedAttributes {
             variables: vec![],
             default: None,
             deserializer: None,
+            interpolation: None,
+            lenient: None,
+            validations: vec![],
+            constraints: vec![],
+            secret: false,
         }
     }
 
@@ -104,33 +146,134 @@ impl ExtractedAttributes {
         }
     }
 
+    /// Sources are checked lazily: the next one is evaluated only if the previous ones are not found.
+    /// The value is returned along with the name of its source.
     fn gen_rest_init(&self, field_name: &str) -> TokenStream {
         let default_span = self.span;
         self.variables.iter().fold(
-            quote_spanned!(default_span=> ::std::option::Option::<::std::string::String>::None),
-            |acc, attribute_init| {
-                let attribute_init = attribute_init.gen_init(field_name);
+            quote_spanned!(default_span=> ::std::option::Option::<(::std::string::String, &::std::primitive::str)>::None),
+            |acc, attribute| {
+                let json = matches!(attribute, FieldAttribute::Config(_));
+                let source = attribute.to_string();
+                let attribute_init = attribute.gen_init(field_name);
                 let span = attribute_init.span();
+                // Only the values of the configuration files and the environment variables are expanded:
+                // secret files, command outputs and the others are taken as is
+                let attribute_init = match &self.interpolation {
+                    Some(interpolation)
+                        if matches!(attribute, FieldAttribute::Config(_) | FieldAttribute::Env(_)) =>
+                    {
+                        interpolation.wrap(attribute_init, json, field_name, self.secret)
+                    }
+                    _ => attribute_init,
+                };
+                let attribute_init = match self.lenient {
+                    Some(lenient)
+                        if matches!(attribute, FieldAttribute::Env(_) | FieldAttribute::Clap(_)) =>
+                    {
+                        lenient.wrap(attribute_init, span)
+                    }
+                    _ => attribute_init,
+                };
+                let attribute_init = quote_spanned! {span=>
+                    (#attribute_init).map(|value| (value, #source))
+                };
                 quote_spanned! {span=>
-                    #acc.or(#attribute_init)
+                    match #acc {
+                        ::std::option::Option::Some(value) => ::std::option::Option::Some(value),
+                        ::std::option::Option::None => #attribute_init,
+                    }
                 }
             },
         )
     }
 
-    pub(super) fn clap_field(self, field_name: &str) -> Result<Option<NormalClapFieldInfo>> {
+    pub(super) fn clap_field(
+        self,
+        field_name: &str,
+        ty: &Type,
+    ) -> Result<Option<NormalClapFieldInfo>> {
         for attr in self.variables {
             if let FieldAttribute::Clap(clap) = attr {
-                return Some(clap.normalize(field_name)).transpose();
+                let count = clap.is_count();
+                let mut info = clap.normalize(field_name)?;
+                let span = info.span;
+                // Counters take no values, clap parses them as `u8` by itself
+                if count {
+                    return Ok(Some(info));
+                }
+                info.attributes
+                    .entry("value_name".to_string())
+                    .or_insert_with(|| str_to_tokens(field_name.to_uppercase(), span));
+                // Values of the secret field must not be printed in the errors of clap,
+                // custom deserializers and lenient parsing may take values in any format
+                if !self.secret && self.deserializer.is_none() && self.lenient.is_none() {
+                    let (ty, optional) = match is_type_an_optional(ty) {
+                        Some(inner) => (inner, true),
+                        None => (ty.clone(), false),
+                    };
+                    info.attributes
+                        .entry("value_parser".to_string())
+                        .or_insert_with(|| {
+                            quote_spanned! {span=> {
+                                #[allow(unused_imports)]
+                                use ::config_manager::__private::{
+                                    DeserializeParser as _, FromStrParser as _, StringParser as _,
+                                    ValueEnumParser as _,
+                                };
+                                (&&&::config_manager::__private::ValueParserProbe::<#ty>::new(#optional))
+                                    .value_parser()
+                            }}
+                        });
+                }
+                if self.secret {
+                    for attr in ["hide_default_value", "hide_possible_values"] {
+                        info.attributes
+                            .entry(attr.to_string())
+                            .or_insert_with(|| quote_spanned!(info.span=> true));
+                    }
+                }
+                return Ok(Some(info));
             }
         }
         Ok(None)
     }
 
+    pub(super) fn config_keys(&self, field_name: &str) -> TokenStream {
+        let keys = self
+            .variables
+            .iter()
+            .filter_map(|attribute| match attribute {
+                FieldAttribute::Config(cfg) => Some(cfg.full_key(field_name)),
+                _ => None,
+            });
+        quote_spanned!(self.span=> ::std::vec![#(#keys),*])
+    }
+
+    pub(super) fn env_keys(&self, field_name: &str) -> TokenStream {
+        let keys = self
+            .variables
+            .iter()
+            .filter_map(|attribute| match attribute {
+                FieldAttribute::Env(env) => Some(env.prefixed_name(field_name)),
+                FieldAttribute::EnvFile(env) => {
+                    let prefixed_name = env.prefixed_name(field_name);
+                    Some(quote_spanned!(env.span=> ::std::format!("{}_file", #prefixed_name)))
+                }
+                _ => None,
+            });
+        quote_spanned!(self.span=> ::std::vec![#(#keys),*])
+    }
+
     pub(super) fn gen_init(&self, field: &Field) -> TokenStream {
         let field_name = field.ident.clone().unwrap().to_string();
         let tp = &field.ty;
+        let is_optional = is_type_an_optional(tp).is_some();
         let default_initialization = match &self.default {
+            // Optional field is `None` if no source provides the value
+            None if is_optional => quote_spanned! {field.span()=>
+                ::std::option::Option::Some::<#tp>(::std::option::Option::None)
+            },
             None => quote_spanned!(field.span()=> ::std::option::Option::None),
             Some(Default { inner: None }) => {
                 quote_spanned!(field.span()=> ::std::option::Option::Some::<#tp>(
@@ -142,25 +285,89 @@ impl ExtractedAttributes {
             },
         };
         let deserializer = self.deserializer();
+        let deserialize_err = if self.secret {
+            // Details of the error may contain parts of the value
+            let msg = format!("Can't deserialize the secret value of field {field_name}");
+            quote_spanned! {field.span()=>
+                |_| ::config_manager::Error::FailedParse(#msg.to_string())
+            }
+        } else if self.deserializer.is_none() {
+            // Invalid values of the enumerations are reported with the expected ones
+            let ty = is_type_an_optional(tp).unwrap_or_else(|| tp.clone());
+            quote_spanned! {field.span()=>
+                |err| ::config_manager::Error::FailedParse(
+                    match ::config_manager::__private::unit_variants::<#ty>() {
+                        ::std::option::Option::Some(variants) => ::std::format!(
+                            "Can't deserialize from value: {} of field {}; expected one of {}",
+                            value, #field_name, variants.join(", ")
+                        ),
+                        ::std::option::Option::None => ::std::format!(
+                            "Can't deserialize from value: {} of field {}; error is {}",
+                            value, #field_name, err
+                        ),
+                    }
+                )
+            }
+        } else {
+            quote_spanned! {field.span()=>
+                |err| ::config_manager::Error::FailedParse(
+                    ::std::format!("Can't deserialize from value: {} of field {}; error is {}", value, #field_name, err)
+                )
+            }
+        };
+        // Null and empty values of the optional field are `None` whatever the source is
+        let null_value = is_optional.then(|| {
+            quote_spanned! {field.span()=>
+                (::std::option::Option::Some((value, source)), _)
+                    if ::config_manager::__private::is_null_or_empty(&value) =>
+                {
+                    (::std::option::Option::None, source)
+                }
+            }
+        });
         let rest = self.gen_rest_init(&field_name);
         let missing_err = self.gen_err(&field_name);
+        let validations = self
+            .validations
+            .iter()
+            .map(|validation| validation.gen_check(&field_name, self.secret))
+            .collect::<TokenStream>();
+        // Only the value of the optional field is validated, `None` is always valid
+        let validations = if validations.is_empty() {
+            validations
+        } else if is_optional {
+            quote_spanned! {field.span()=>
+                if let ::std::option::Option::Some(value) = &value {
+                    #validations
+                }
+            }
+        } else {
+            quote_spanned! {field.span()=>
+                {
+                    let value = &value;
+                    #validations
+                }
+            }
+        };
 
         quote_spanned! {field.span()=>
             (|| -> ::std::result::Result<_, ::config_manager::Error> {
                 let init_without_default = #rest;
-                match (init_without_default, #default_initialization) {
-                    (::std::option::Option::<::std::string::String>::None, ::std::option::Option::None) => {
+                // Source is used in the errors of the validations only
+                #[allow(unused_variables)]
+                let (value, source) = match (init_without_default, #default_initialization) {
+                    (::std::option::Option::None, ::std::option::Option::None) => {
                             ::std::result::Result::<_, ::config_manager::Error>::Err(#missing_err)?
                         },
-                    (::std::option::Option::<::std::string::String>::None, ::std::option::Option::Some(default_value)) => ::std::result::Result::Ok(default_value),
-                    (::std::option::Option::<::std::string::String>::Some(value), _) => {
-                        #deserializer.map_err(|err| {
-                            ::config_manager::Error::FailedParse(
-                                ::std::format!("Can't deserialize from value: {} of field {}; error is {}", value, #field_name, err)
-                            )
-                        })
+                    (::std::option::Option::None, ::std::option::Option::Some(default_value)) => (default_value, "default"),
+                    #null_value
+                    (::std::option::Option::Some((value, source)), _) => {
+                        supplied.insert(#field_name, source);
+                        #deserializer.map_err(#deserialize_err).map(|value| (value, source))?
                     }
-                }
+                };
+                #validations
+                ::std::result::Result::Ok(value)
             })()?
         }
     }
@@ -171,14 +378,21 @@ pub(crate) enum FieldAttribute {
     Clap(ClapFieldParseResult),
     Env(Env),
     Config(Config),
+    EnvFile(Env),
+    SecretFile(SecretFile),
+    Exec(Exec),
+    Custom(Custom),
 }
 
 impl FieldAttribute {
     fn span(&self) -> Span {
         match self {
             Self::Clap(v) => v.span,
-            Self::Env(v) => v.span,
+            Self::Env(v) | Self::EnvFile(v) => v.span,
             Self::Config(v) => v.span,
+            Self::SecretFile(v) => v.span,
+            Self::Exec(v) => v.span,
+            Self::Custom(v) => v.span,
         }
     }
 
@@ -194,8 +408,34 @@ impl FieldAttribute {
             Self::Config(cfg) => {
                 let table = cfg.table();
                 let key = cfg.key(field_name);
+                if cfg.relative_to_file {
+                    quote_spanned! {span=>
+                        ::config_manager::__private::find_path_in_table(config_file_data, #table, #key.to_string())?
+                    }
+                } else {
+                    quote_spanned! {span=>
+                        ::config_manager::__private::find_field_in_table(config_file_data.values(), #table, #key.to_string())?
+                    }
+                }
+            }
+            Self::Clap(clap) if clap.is_count() => {
+                let long = clap.normal_long(field_name);
+                // Counter is always set by clap (0 by default), so it's found only if the flag is passed
+                quote_spanned! {span=>
+                    (clap_data.value_source(#long)
+                        == ::std::option::Option::Some(::config_manager::__private::clap::parser::ValueSource::CommandLine))
+                    .then(|| clap_data.get_count(#long).to_string())
+                }
+            }
+            Self::Clap(clap) if clap.is_negatable() => {
+                let long = clap.normal_long(field_name);
+                let negated = clap.negated_long(field_name);
                 quote_spanned! {span=>
-                    ::config_manager::__private::find_field_in_table(config_file_data, #table, #key.to_string())?
+                    if clap_data.get_flag(#negated) {
+                        ::std::option::Option::Some("false".to_string())
+                    } else {
+                        clap_data.get_one::<::std::string::String>(#long).map(::std::string::ToString::to_string)
+                    }
                 }
             }
             Self::Clap(clap) => {
@@ -204,6 +444,45 @@ impl FieldAttribute {
                     clap_data.get_one::<::std::string::String>(#long).map(::std::string::ToString::to_string)
                 }
             }
+            Self::EnvFile(env) => {
+                let prefixed_name = env.prefixed_name(field_name);
+                quote_spanned! {span=>
+                    ::config_manager::__private::read_env_file(env_data, &::std::format!("{}_file", #prefixed_name))?
+                }
+            }
+            Self::SecretFile(secret) => {
+                let dir = secret
+                    .dir
+                    .clone()
+                    .unwrap_or_else(|| quote_spanned!(span=> "/run/secrets"));
+                let name = secret
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| str_to_tokens(field_name, span));
+                quote_spanned! {span=>
+                    ::config_manager::__private::read_secret_file(#dir, #name)?
+                }
+            }
+            Self::Exec(exec) => {
+                let command = &exec.command;
+                let timeout = &exec.timeout;
+                quote_spanned! {span=>
+                    ::config_manager::__private::exec_command(
+                        &[#(::std::convert::AsRef::<::std::primitive::str>::as_ref(&#command)),*],
+                        ::std::time::Duration::from_secs(#timeout),
+                    )?
+                }
+            }
+            Self::Custom(custom) => {
+                let path = &custom.path;
+                let key = custom
+                    .key
+                    .clone()
+                    .unwrap_or_else(|| str_to_tokens(field_name, span));
+                quote_spanned! {span=>
+                    custom_sources.get::<#path>(#key)?
+                }
+            }
         }
     }
 }
@@ -214,6 +493,10 @@ impl Display for FieldAttribute {
             Self::Clap(_) => "command line",
             Self::Config(_) => "configuration file",
             Self::Env(_) => "env",
+            Self::EnvFile(_) => "file set by env",
+            Self::SecretFile(_) => "secrets directory",
+            Self::Exec(_) => "command output",
+            Self::Custom(_) => "custom source",
         };
         write!(f, "{source}",)
     }
@@ -272,6 +555,7 @@ pub(crate) struct Config {
     span: Span,
     pub(super) key: Option<TokenStream>,
     pub(super) table: Option<TokenStream>,
+    pub(super) relative_to_file: bool,
 }
 
 impl std::default::Default for Config {
@@ -280,6 +564,7 @@ impl std::default::Default for Config {
             span: Span::call_site(),
             key: None,
             table: None,
+            relative_to_file: false,
         }
     }
 }
@@ -290,6 +575,21 @@ impl Config {
             .clone()
             .unwrap_or_else(|| str_to_tokens(field_name, self.span))
     }
+    /// Dotted path of the key along with the table.
+    fn full_key(&self, field_name: &str) -> TokenStream {
+        let key = self.key(field_name);
+        let table = self.table();
+        quote_spanned! {self.span=>
+            {
+                let table: ::std::option::Option<::std::string::String> = #table;
+                match table {
+                    ::std::option::Option::Some(table) => ::std::format!("{}.{}", table, #key),
+                    ::std::option::Option::None => #key.to_string(),
+                }
+            }
+        }
+    }
+
     fn table(&self) -> TokenStream {
         self.table
             .clone()
@@ -298,6 +598,437 @@ impl Config {
     }
 }
 
+#[derive(Clone)]
+pub(crate) struct SecretFile {
+    span: Span,
+    name: Option<TokenStream>,
+    dir: Option<TokenStream>,
+}
+
+#[derive(Clone)]
+pub(crate) struct Exec {
+    span: Span,
+    command: Vec<Expr>,
+    timeout: TokenStream,
+}
+
+impl Exec {
+    const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+    fn parse(arg: &Meta) -> Result<Self> {
+        let usage = "exec attribute must match `exec = [\"program\", \"arg\", ...]` \
+            or `exec(command = [...], timeout = seconds)`";
+        let mut command = None;
+        let mut timeout = None;
+        match arg {
+            Meta::NameValue(MetaNameValue { value, .. }) => command = Some(value.clone()),
+            Meta::List(list) => {
+                let nested =
+                    list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+                for nested in nested {
+                    match (path_to_string(nested.path()).as_str(), nested) {
+                        ("command", Meta::NameValue(MetaNameValue { value, .. }))
+                            if command.is_none() =>
+                        {
+                            command = Some(value)
+                        }
+                        ("timeout", Meta::NameValue(MetaNameValue { value, .. }))
+                            if timeout.is_none() =>
+                        {
+                            timeout = Some(value.to_token_stream())
+                        }
+                        (_, nested) => panic_span!(nested.span(), "{usage}"),
+                    }
+                }
+            }
+            Meta::Path(_) => panic_span!(arg.span(), "{usage}"),
+        }
+
+        let command = match command {
+            Some(Expr::Array(array)) if !array.elems.is_empty() => {
+                array.elems.into_iter().collect()
+            }
+            Some(Expr::Array(array)) => panic_span!(array.span(), "exec command can't be empty"),
+            _ => panic_span!(arg.span(), "{usage}"),
+        };
+        let timeout = timeout.unwrap_or_else(|| {
+            let timeout = Self::DEFAULT_TIMEOUT_SECS;
+            quote_spanned!(arg.span()=> #timeout)
+        });
+
+        Ok(Self {
+            span: arg.span(),
+            command,
+            timeout,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Custom {
+    span: Span,
+    path: Path,
+    key: Option<TokenStream>,
+}
+
+impl Custom {
+    fn parse(arg: &Meta) -> Result<Self> {
+        let usage = "custom attribute must match `custom(path = SourceType, key = \"...\")`";
+        let list = arg
+            .require_list()
+            .map_err(|_| Error::new(arg.span(), usage))?;
+        let nested = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+        let mut path = None;
+        let mut key = None;
+        for nested in nested {
+            match (path_to_string(nested.path()).as_str(), &nested) {
+                (
+                    "path",
+                    Meta::NameValue(MetaNameValue {
+                        value: Expr::Path(source),
+                        ..
+                    }),
+                ) if path.is_none() => path = Some(source.path.clone()),
+                ("key", Meta::NameValue(meta_value_lit!(lit_str))) if key.is_none() => {
+                    key = Some(lit_str.to_token_stream())
+                }
+                _ => panic_span!(nested.span(), "{usage}"),
+            }
+        }
+
+        Ok(Self {
+            span: arg.span(),
+            path: path.err_on_none(arg.span(), "custom source must have the `path` attribute")?,
+            key,
+        })
+    }
+}
+
+/// `${VAR}` expansion in the raw values of the field.
+#[derive(Clone)]
+pub(crate) struct Interpolation {
+    span: Span,
+    /// Variables can refer to the keys of the configuration files too.
+    config: bool,
+}
+
+impl Interpolation {
+    pub(crate) fn parse(arg: &Meta) -> Result<Self> {
+        let usage = "interpolate attribute must match `interpolate` or `interpolate(config)`";
+        let config = match arg {
+            Meta::Path(_) => false,
+            Meta::List(list) => {
+                let nested =
+                    list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+                match nested.into_iter().collect::<Vec<_>>().as_slice() {
+                    [Meta::Path(path)] if path.is_ident(CONFIG_KEY) => true,
+                    _ => panic_span!(arg.span(), "{usage}"),
+                }
+            }
+            Meta::NameValue(_) => panic_span!(arg.span(), "{usage}"),
+        };
+        Ok(Self {
+            span: arg.span(),
+            config,
+        })
+    }
+
+    /// Values of the configuration files are json, only their strings are expanded.
+    /// The value of the `secret` field is masked in the errors.
+    fn wrap(&self, init: TokenStream, json: bool, field_name: &str, secret: bool) -> TokenStream {
+        let span = self.span;
+        let config = if self.config {
+            quote_spanned!(span=> ::std::option::Option::Some(config_file_data))
+        } else {
+            quote_spanned!(span=> ::std::option::Option::None)
+        };
+        let interpolate = quote_spanned! {span=>
+            ::config_manager::__private::interpolate(value, #json, env_data, #config, #field_name)
+        };
+        let interpolate = if secret {
+            quote_spanned! {span=>
+                {
+                    let raw = value.clone();
+                    #interpolate.map_err(|err| ::config_manager::__private::redact_error(err, &raw))
+                }
+            }
+        } else {
+            interpolate
+        };
+        quote_spanned! {span=>
+            (#init)
+                .map(|value| #interpolate)
+                .transpose()?
+        }
+    }
+}
+
+/// Shell conventions accepted in the env and command line values of the boolean and integer fields.
+#[derive(Clone, Copy)]
+pub(crate) enum Lenient {
+    Bool,
+    Integer,
+}
+
+impl Lenient {
+    /// `None` if the type (of `T` for `Option<T>`) is neither boolean nor integer.
+    pub(crate) fn of(ty: &Type) -> Option<Self> {
+        let ty = is_type_an_optional(ty).unwrap_or_else(|| ty.clone());
+        if ty.to_token_stream().to_string() == "bool" {
+            Some(Self::Bool)
+        } else if is_integer(&ty) {
+            Some(Self::Integer)
+        } else {
+            None
+        }
+    }
+
+    fn wrap(self, init: TokenStream, span: Span) -> TokenStream {
+        match self {
+            Self::Bool => quote_spanned! {span=>
+                (#init).map(::config_manager::__private::lenient_bool)
+            },
+            Self::Integer => quote_spanned! {span=>
+                (#init).map(::config_manager::__private::lenient_int)
+            },
+        }
+    }
+}
+
+/// Rule of the `validate(...)` attribute, checked after deserialization.
+#[derive(Clone)]
+pub(crate) struct Validation {
+    span: Span,
+    kind: ValidationKind,
+    /// The rule as it is written in the attribute, for the error messages.
+    rule: String,
+}
+
+#[derive(Clone)]
+enum ValidationKind {
+    Range(Expr),
+    MinLen(Expr),
+    MaxLen(Expr),
+    Regex(LitStr),
+    With(Expr),
+}
+
+impl Validation {
+    fn parse(arg: &Meta) -> Result<Vec<Self>> {
+        let usage =
+            "validate attribute must match `validate(range = ..., min_len = ..., max_len = ..., \
+            regex = \"...\", with = function)`";
+        let list = arg
+            .require_list()
+            .map_err(|_| Error::new(arg.span(), usage))?;
+        let nested = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if nested.is_empty() {
+            panic_span!(arg.span(), "{usage}")
+        }
+
+        nested
+            .into_iter()
+            .map(|rule| {
+                let value = match &rule {
+                    Meta::NameValue(MetaNameValue { value, .. }) => value.clone(),
+                    _ => panic_span!(rule.span(), "{usage}"),
+                };
+                let name = path_to_string(rule.path());
+                let kind = match (name.as_str(), value.clone()) {
+                    ("range", value) => ValidationKind::Range(value),
+                    ("min_len", value) => ValidationKind::MinLen(value),
+                    ("max_len", value) => ValidationKind::MaxLen(value),
+                    (
+                        "regex",
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }),
+                    ) => {
+                        if let Err(err) = regex::Regex::new(&lit.value()) {
+                            panic_span!(lit.span(), "invalid regex: {err}")
+                        }
+                        ValidationKind::Regex(lit)
+                    }
+                    ("with", value) => ValidationKind::With(value),
+                    _ => panic_span!(rule.span(), "{usage}"),
+                };
+                Ok(Self {
+                    span: rule.span(),
+                    kind,
+                    rule: format!("{name} = {}", value.to_token_stream()),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the error from the initialization closure if `value` (a reference) violates the rule.
+    /// Errors of the `with` function may contain the value, so they are omitted for the `secret` field.
+    fn gen_check(&self, field_name: &str, secret: bool) -> TokenStream {
+        let span = self.span;
+        let violation = format!("violates `{}`", self.rule);
+        let error = |message: TokenStream| {
+            quote_spanned! {span=>
+                return ::std::result::Result::Err(::config_manager::Error::ValidationFailed {
+                    field: #field_name.to_string(),
+                    source: source.to_string(),
+                    message: #message,
+                })
+            }
+        };
+        let violated = error(quote_spanned!(span=> #violation.to_string()));
+
+        match &self.kind {
+            ValidationKind::Range(range) => quote_spanned! {span=>
+                if !::std::ops::RangeBounds::contains(&(#range), value) {
+                    #violated
+                }
+            },
+            ValidationKind::MinLen(min) => quote_spanned! {span=>
+                if ::config_manager::__private::ValueLen::value_len(value) < #min {
+                    #violated
+                }
+            },
+            ValidationKind::MaxLen(max) => quote_spanned! {span=>
+                if ::config_manager::__private::ValueLen::value_len(value) > #max {
+                    #violated
+                }
+            },
+            // The regex is checked by the macro and compiled once
+            ValidationKind::Regex(regex) => quote_spanned! {span=>
+                {
+                    static REGEX: ::std::sync::OnceLock<::config_manager::__private::regex::Regex> =
+                        ::std::sync::OnceLock::new();
+                    let regex = REGEX.get_or_init(|| {
+                        ::config_manager::__private::regex::Regex::new(#regex)
+                            .expect("regex is checked by the macro")
+                    });
+                    if !regex.is_match(::std::convert::AsRef::<::std::primitive::str>::as_ref(value)) {
+                        #violated
+                    }
+                }
+            },
+            ValidationKind::With(function) if secret => quote_spanned! {span=>
+                if (#function)(value).is_err() {
+                    #violated
+                }
+            },
+            ValidationKind::With(function) => {
+                let failed =
+                    error(quote_spanned!(span=> ::std::format!("{}: {}", #violation, err)));
+                quote_spanned! {span=>
+                    if let ::std::result::Result::Err(err) = (#function)(value) {
+                        #failed
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Constraint between the fields, checked over the resolved values of the structure.
+/// The field is supplied if its value is taken from any source but the default.
+#[derive(Clone)]
+pub(crate) struct FieldConstraint {
+    field: String,
+    kind: ConstraintKind,
+}
+
+#[derive(Clone)]
+enum ConstraintKind {
+    /// The field must be supplied if the value of the other field equals to the expression.
+    RequiredIf { other: Ident, eq: Expr },
+    /// The field and the other one can't be supplied at the same time.
+    ConflictsWith(Ident),
+}
+
+impl FieldConstraint {
+    fn parse(arg: &Meta, field: &str) -> Result<Self> {
+        let field = field.to_string();
+        if arg.path().is_ident(CONFLICTS_WITH_KEY) {
+            let other = match arg {
+                Meta::NameValue(meta_value_lit!(other)) => other.parse::<Ident>()?,
+                _ => panic_span!(
+                    arg.span(),
+                    "{CONFLICTS_WITH_KEY} attribute must match `{CONFLICTS_WITH_KEY} = \"field\"`"
+                ),
+            };
+            return Ok(Self {
+                field,
+                kind: ConstraintKind::ConflictsWith(other),
+            });
+        }
+
+        let usage = format!("{REQUIRED_IF_KEY} attribute must match `{REQUIRED_IF_KEY}(field = \"field\", eq = value)`");
+        let list = arg
+            .require_list()
+            .map_err(|_| Error::new(arg.span(), &usage))?;
+        let nested =
+            list.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+        let mut other = None;
+        let mut eq = None;
+        for arg in nested {
+            match (path_to_string(&arg.path).as_str(), &arg) {
+                ("field", meta_value_lit!(field)) if other.is_none() => {
+                    other = Some(field.parse::<Ident>()?)
+                }
+                ("eq", _) if eq.is_none() => eq = Some(arg.value),
+                _ => panic_span!(arg.path.span(), "{usage}"),
+            }
+        }
+
+        Ok(Self {
+            field,
+            kind: ConstraintKind::RequiredIf {
+                other: other.err_on_none(list.span(), &usage)?,
+                eq: eq.err_on_none(list.span(), &usage)?,
+            },
+        })
+    }
+
+    /// The other field of the constraint.
+    pub(crate) fn other(&self) -> &Ident {
+        match &self.kind {
+            ConstraintKind::RequiredIf { other, .. } | ConstraintKind::ConflictsWith(other) => {
+                other
+            }
+        }
+    }
+
+    /// Returns the error from the structure initialization if the constraint is violated.
+    /// `res` is the initialized structure, `supplied` maps the supplied fields to their sources.
+    pub(crate) fn gen_check(&self) -> TokenStream {
+        let field = &self.field;
+        let other = self.other();
+        let span = other.span();
+        let other_name = other.to_string();
+        match &self.kind {
+            ConstraintKind::RequiredIf { eq, .. } => {
+                let msg = format!(
+                    "field {field} is required when {other_name} is {}",
+                    eq.to_token_stream()
+                );
+                quote_spanned! {span=>
+                    if res.#other == #eq && !supplied.contains_key(#field) {
+                        return ::std::result::Result::Err(::config_manager::Error::MissingArgument(#msg.to_string()));
+                    }
+                }
+            }
+            ConstraintKind::ConflictsWith(_) => quote_spanned! {span=>
+                if let (::std::option::Option::Some(source), ::std::option::Option::Some(other_source)) =
+                    (supplied.get(#field), supplied.get(#other_name))
+                {
+                    return ::std::result::Result::Err(::config_manager::Error::ValidationFailed {
+                        field: #field.to_string(),
+                        source: source.to_string(),
+                        message: ::std::format!("conflicts with field {} taken from {}", #other_name, other_source),
+                    });
+                }
+            },
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub(crate) struct Default {
     pub(super) inner: Option<TokenStream>,
@@ -306,10 +1037,12 @@ pub(crate) struct Default {
 pub(super) fn extract_attributes(
     field: &Field,
     table_name: &Option<TokenStream>,
+    secrets_dir: &Option<TokenStream>,
 ) -> Result<Option<ExtractedAttributes>> {
     let is_bool = field.ty.to_token_stream().to_string() == "bool";
     let is_string = is_string(&field.ty);
     let docs = extract_docs(&field.attrs);
+    let field_name = field.ident.clone().unwrap().to_string();
 
     let mut res = ExtractedAttributes::new(field.span());
 
@@ -319,6 +1052,7 @@ pub(super) fn extract_attributes(
     };
 
     let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+    let mut relative_to_file = None;
 
     for arg in nested {
         match path_to_string(arg.path()).as_str() {
@@ -328,6 +1062,12 @@ pub(super) fn extract_attributes(
                     .push(FieldAttribute::Clap(ClapFieldParseResult::new(arg.span()))),
                 Meta::List(clap_metalist) => {
                     let mut clap_attributes = parse_clap_field_attribute(clap_metalist, is_bool)?;
+                    if clap_attributes.is_count() && !is_integer(&field.ty) {
+                        panic_span!(
+                            clap_metalist.span(),
+                            "Only integer arguments can be counted"
+                        )
+                    }
                     clap_attributes.docs = docs.clone();
                     res.variables.push(FieldAttribute::Clap(clap_attributes));
                 }
@@ -359,7 +1099,60 @@ pub(super) fn extract_attributes(
                 span: arg.span(),
                 key: meta_to_option(&arg)?,
                 table: table_name.clone(),
+                relative_to_file: false,
+            })),
+            ENV_FILE_KEY => res.variables.push(FieldAttribute::EnvFile(Env {
+                inner: meta_to_option(&arg)?,
+                span: arg.span(),
+            })),
+            SECRET_FILE_KEY => res.variables.push(FieldAttribute::SecretFile(SecretFile {
+                span: arg.span(),
+                name: meta_to_option(&arg)?,
+                dir: secrets_dir.clone(),
             })),
+            EXEC_KEY => res.variables.push(FieldAttribute::Exec(Exec::parse(&arg)?)),
+            CUSTOM_KEY => res
+                .variables
+                .push(FieldAttribute::Custom(Custom::parse(&arg)?)),
+            VALIDATE_KEY => res.validations.extend(Validation::parse(&arg)?),
+            LENIENT_KEY => {
+                if !matches!(arg, Meta::Path(_)) {
+                    panic_span!(arg.span(), "{LENIENT_KEY} cannot take values")
+                }
+                res.lenient = Some(Lenient::of(&field.ty).ok_or_else(|| {
+                    Error::new(
+                        arg.span(),
+                        "lenient can be used only on boolean and integer fields",
+                    )
+                })?);
+            }
+            SECRET_KEY => {
+                if !matches!(arg, Meta::Path(_)) {
+                    panic_span!(arg.span(), "{SECRET_KEY} cannot take values")
+                }
+                res.secret = true;
+            }
+            REQUIRED_IF_KEY | CONFLICTS_WITH_KEY => res
+                .constraints
+                .push(FieldConstraint::parse(&arg, &field_name)?),
+            INTERPOLATE_KEY => {
+                if res.interpolation.is_some() {
+                    panic_span!(arg.span(), "interpolate can be set only once per field")
+                }
+                res.interpolation = Some(Interpolation::parse(&arg)?);
+            }
+            RELATIVE_TO_FILE => {
+                if relative_to_file.is_some() {
+                    panic_span!(
+                        arg.span(),
+                        "relative_to_file can be set only once per field"
+                    )
+                }
+                if !matches!(arg, Meta::Path(_)) {
+                    panic_span!(arg.span(), "relative_to_file cannot take values")
+                }
+                relative_to_file = Some(arg.span());
+            }
             DESERIALIZER => {
                 if res.deserializer.is_some() {
                     panic_span!(
@@ -376,6 +1169,22 @@ pub(super) fn extract_attributes(
         };
     }
 
+    if let Some(span) = relative_to_file {
+        let mut has_config = false;
+        for variable in &mut res.variables {
+            if let FieldAttribute::Config(cfg) = variable {
+                cfg.relative_to_file = true;
+                has_config = true;
+            }
+        }
+        if !has_config {
+            panic_span!(
+                span,
+                "relative_to_file can be used only along with the config source"
+            )
+        }
+    }
+
     Ok(Some(res))
 }
 
@@ -399,3 +1208,11 @@ fn is_string(ty: &Type) -> bool {
     .into_iter()
     .any(|s| idents_of_path == *s)
 }
+
+fn is_integer(ty: &Type) -> bool {
+    let ty = is_type_an_optional(ty).unwrap_or_else(|| ty.clone());
+    [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ]
+    .contains(&ty.to_token_stream().to_string().as_str())
+}
@@ -239,6 +239,23 @@ pub(crate) fn parse_clap_field_attribute(
         }
     }
 
+    if res.is_negatable() {
+        if matches!(res.attributes.get("flag").unwrap(), ClapOption::None) {
+            panic_span!(
+                attributes.span(),
+                "clap(negatable) can be used only along with clap(flag)"
+            )
+        }
+        if let ClapOption::Some(long) = res.attributes.get("long").unwrap() {
+            if syn::parse2::<LitStr>(long.clone()).is_err() {
+                panic_span!(
+                    long.span(),
+                    "long name of the negatable flag must be a string literal"
+                )
+            }
+        }
+    }
+
     Ok(res)
 }
 
@@ -260,6 +277,24 @@ impl ClapFieldParseResult {
         }
     }
 
+    /// The argument is a counter of its occurrences (`-vvv`).
+    pub(crate) fn is_count(&self) -> bool {
+        !matches!(self.attributes.get("count"), None | Some(ClapOption::None))
+    }
+
+    /// The flag can be switched off with the `--no-<long>` argument.
+    pub(crate) fn is_negatable(&self) -> bool {
+        !matches!(
+            self.attributes.get("negatable"),
+            None | Some(ClapOption::None)
+        )
+    }
+
+    /// Name of the `--no-<long>` argument of the negatable flag.
+    pub(crate) fn negated_long(&self, field_name: &str) -> TokenStream {
+        negated_long(&self.normal_long(field_name), self.span)
+    }
+
     pub(crate) fn has_explicit_long(&self) -> bool {
         matches!(self.attributes.get("long").unwrap(), ClapOption::Some(_))
     }
@@ -305,3 +340,10 @@ impl ClapFieldParseResult {
         })
     }
 }
+
+/// `long` of the negatable flag is checked to be a string literal while parsing.
+pub(crate) fn negated_long(long: &TokenStream, span: Span) -> TokenStream {
+    let long = syn::parse2::<LitStr>(long.clone())
+        .expect("long name of the negatable flag is a string literal");
+    LitStr::new(&format!("no-{}", long.value()), span).to_token_stream()
+}
@@ -4,7 +4,7 @@
 use std::collections::HashMap;
 
 use super::{attributes::*, meta_value_lit};
-use crate::utils::field::utils::{ExtractedAttributes, FieldAttribute};
+use crate::utils::field::utils::{ExtractedAttributes, FieldAttribute, Interpolation};
 use crate::*;
 
 pub(crate) struct AppTopLevelInfo {
@@ -14,6 +14,14 @@ pub(crate) struct AppTopLevelInfo {
     pub(crate) debug_cmd_input: Option<TokenStream>,
     pub(crate) table_name: Option<TokenStream>,
     pub(crate) default_order: Option<ExtractedAttributes>,
+    pub(crate) secrets_dir: Option<TokenStream>,
+    pub(crate) interpolation: Option<Interpolation>,
+    /// Shell conventions are accepted in the env and command line values of the boolean and integer fields.
+    pub(crate) lenient: bool,
+    pub(crate) deny_unknown_keys: bool,
+    /// `Some(true)` if the unused environment variables are an error, `Some(false)` if they are warnings.
+    pub(crate) unused_env: Option<bool>,
+    pub(crate) validation: Option<Expr>,
 }
 
 impl AppTopLevelInfo {
@@ -26,6 +34,12 @@ impl AppTopLevelInfo {
             debug_cmd_input: extract_debug_cmd_input(crate_attrs)?,
             table_name: extract_table_name(crate_attrs)?,
             default_order: extract_source_order(crate_attrs)?,
+            secrets_dir: extract_secrets_dir(crate_attrs)?,
+            interpolation: extract_interpolation(crate_attrs)?,
+            lenient: extract_lenient(crate_attrs)?,
+            deny_unknown_keys: extract_deny_unknown_keys(crate_attrs)?,
+            unused_env: extract_unused_env(crate_attrs)?,
+            validation: extract_struct_validation(crate_attrs)?,
         })
     }
 }
@@ -93,20 +107,82 @@ pub(crate) fn extract_debug_cmd_input(attrs: &[Meta]) -> Result<Option<TokenStre
 }
 
 pub(crate) fn extract_table_name(attrs: &[Meta]) -> Result<Option<TokenStream>> {
-    let meta = match attrs.iter().find(|a| a.path().is_ident(TABLE_NAME_KEY)) {
+    extract_str_attr(attrs, TABLE_NAME_KEY)
+}
+
+pub(crate) fn extract_secrets_dir(attrs: &[Meta]) -> Result<Option<TokenStream>> {
+    extract_str_attr(attrs, SECRETS_DIR_KEY)
+}
+
+pub(crate) fn extract_interpolation(attrs: &[Meta]) -> Result<Option<Interpolation>> {
+    attrs
+        .iter()
+        .find(|a| a.path().is_ident(INTERPOLATE_KEY))
+        .map(Interpolation::parse)
+        .transpose()
+}
+
+/// Function of the `validate = ...` attribute checking the initialized structure.
+/// Outer attributes can't take paths as values, so `#[validate(function)]` form is accepted too.
+pub(crate) fn extract_struct_validation(attrs: &[Meta]) -> Result<Option<Expr>> {
+    match attrs.iter().find(|a| a.path().is_ident(VALIDATE_KEY)) {
+        None => Ok(None),
+        Some(Meta::NameValue(MetaNameValue { value, .. })) => Ok(Some(value.clone())),
+        Some(Meta::List(list)) => Ok(Some(list.parse_args()?)),
+        Some(meta) => panic_span!(
+            meta.span(),
+            "{VALIDATE_KEY} attribute must match `{VALIDATE_KEY} = function` or `{VALIDATE_KEY}(function)`"
+        ),
+    }
+}
+
+pub(crate) fn extract_lenient(attrs: &[Meta]) -> Result<bool> {
+    match attrs.iter().find(|a| a.path().is_ident(LENIENT_KEY)) {
+        None => Ok(false),
+        Some(Meta::Path(_)) => Ok(true),
+        Some(meta) => panic_span!(meta.span(), "{LENIENT_KEY} can't take values"),
+    }
+}
+
+pub(crate) fn extract_deny_unknown_keys(attrs: &[Meta]) -> Result<bool> {
+    match attrs
+        .iter()
+        .find(|a| a.path().is_ident(DENY_UNKNOWN_KEYS_KEY))
+    {
+        None => Ok(false),
+        Some(Meta::Path(_)) => Ok(true),
+        Some(meta) => panic_span!(meta.span(), "{DENY_UNKNOWN_KEYS_KEY} can't take values"),
+    }
+}
+
+pub(crate) fn extract_unused_env(attrs: &[Meta]) -> Result<Option<bool>> {
+    let meta = match attrs.iter().find(|a| a.path().is_ident(UNUSED_ENV_KEY)) {
         None => return Ok(None),
         Some(meta) => meta,
     };
 
     match meta {
-        Meta::NameValue(meta_value_lit!(lit_str)) => Ok(Some(lit_str.to_token_stream())),
+        Meta::NameValue(meta_value_lit!(lit_str)) if lit_str.value() == "warn" => Ok(Some(false)),
+        Meta::NameValue(meta_value_lit!(lit_str)) if lit_str.value() == "deny" => Ok(Some(true)),
         _ => panic_span!(
             meta.span(),
-            "{TABLE_NAME_KEY} must match #[{TABLE_NAME_KEY} = \"...\"]"
+            "{UNUSED_ENV_KEY} must match #[{UNUSED_ENV_KEY} = \"warn\"] or #[{UNUSED_ENV_KEY} = \"deny\"]"
         ),
     }
 }
 
+fn extract_str_attr(attrs: &[Meta], key: &str) -> Result<Option<TokenStream>> {
+    let meta = match attrs.iter().find(|a| a.path().is_ident(key)) {
+        None => return Ok(None),
+        Some(meta) => meta,
+    };
+
+    match meta {
+        Meta::NameValue(meta_value_lit!(lit_str)) => Ok(Some(lit_str.to_token_stream())),
+        _ => panic_span!(meta.span(), "{key} must match #[{key} = \"...\"]"),
+    }
+}
+
 pub(crate) fn extract_source_order(attrs: &[Meta]) -> Result<Option<ExtractedAttributes>> {
     let meta = match attrs.iter().find(|m| m.path().is_ident(SOURCE_ORDER_KEY)) {
         None => return Ok(None),
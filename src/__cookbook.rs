@@ -8,19 +8,36 @@
 //! 4. [Structure level attributes](#structure-attributes)
 //!     1. [env_prefix](#env_prefix)
 //!     2. [file](#file)
-//!     3. [clap](#clap)
-//!     4. [table](#table)
-//!     5. [default source order](#default_order)
+//!     3. [dotenv](#dotenv)
+//!     4. [profiles](#profiles)
+//!     5. [clap](#clap)
+//!     6. [table](#table)
+//!     7. [default source order](#default_order)
+//!     8. [interpolate](#interpolate)
+//!     9. [lenient](#lenient)
+//!     10. [deny_unknown_keys](#deny_unknown_keys)
+//!     11. [unused_env](#unused_env)
+//!     12. [validate](#validate)
 //! 5. [Field level attributes](#field-attributes)
 //!     1. [source](#source)
 //!         - [default](#default)
 //!         - [env](#env)
 //!         - [config](#config)
+//!         - [env_file](#env_file)
+//!         - [secret_file](#secret_file)
+//!         - [exec](#exec)
+//!         - [custom](#custom)
 //!         - [clap](#clap-1)
 //!         - [deserialize_with](#deserialize_with)
+//!         - [validate](#validate-1)
+//!         - [constraints](#constraints)
+//!         - [secret](#secret)
 //!     2. [flatten](#flatten)
 //!         - [attributes](#flatten-attributes)
 //!     3. [subcommand](#subcommand)
+//!     4. [config_files](#config-files)
+//!     5. [config_profile](#config-profile)
+//!     6. [unused_env](#unused-env)
 //! 6. [`get_command` method](#get_command)
 //! # [Appendix](#appendix)
 //! 1. [Allowed clap attributes](#clap-attributes)
@@ -88,19 +105,22 @@
 //! In this example, the `env` source will be checked first.
 //!
 //! **NOTES**
-//! - The possible sources are: `clap`, `env`, `config`, `default` (see below)
+//! - The possible sources are: `clap`, `env`, `config`, `env_file`, `secret_file`, `exec`, `custom`, `default` (see below)
 //! - Default value will be assigned the last (after the others were not found).
 //! - If the value is not found in any of the sources, an error will be returned
+//!   (`Option<T>` fields without `default` are `None` instead)
+//! - Null and empty values of `Option<T>` fields are `None` whatever the source is
+//!   (like `--field ""`, `APP_FIELD=`, `APP_FIELD=null` or `field: ~` in the configuration file)
 //! - Field type must implement `serde::de::Deserialize`
 //! - All attributes except `default` must match either `attribute = literal`, or
-//!     `attribute(init_from = ...valid Rust code...)`, or `attribute`. In the last case, the "key"
-//!     value (the CLI argument name, the environment variable name, or the config file key name —
-//!     depending on the source) will match the field name. For example, annotating `my_field` with
-//!     `#[clap]` means that the value could be assigned to `my_field` by specifying
-//!     `--my_field=...` via the CLI
+//!   `attribute(init_from = ...valid Rust code...)`, or `attribute`. In the last case, the "key"
+//!   value (the CLI argument name, the environment variable name, or the config file key name —
+//!   depending on the source) will match the field name. For example, annotating `my_field` with
+//!   `#[clap]` means that the value could be assigned to `my_field` by specifying
+//!   `--my_field=...` via the CLI
 //! - Attribute `default` must match `default = ...valid Rust code...` or `default`
 //! - If the `deserialize_with` attribute is not set, values from command line,
-//!     environment will be deserialized according to [hjson syntax](https://hjson.github.io/)
+//!   environment will be deserialized according to [hjson syntax](https://hjson.github.io/)
 //!
 //! ## Options
 //! Parsing process may be run with a set of options by using the [ConfigInit::parse_options(options)](../trait.ConfigInit.html#tymethod.parse_options).
@@ -126,7 +146,7 @@
 //! - The delimiter ('_') is placed automatically
 //! - `env_prefix = ""` will not add any prefix
 //! - `env`, `env_prefix` and similar attributes are case-insensitive. If both the `demo_iter` and
-//!     `DEMO_ITER` environment variables are present, which of these two will be parsed *is not defined*
+//!   `DEMO_ITER` environment variables are present, which of these two will be parsed *is not defined*
 //! - One can use `env_prefix` (without a value) to set the binary file name as a prefix
 //!
 //! **Example**
@@ -149,12 +169,55 @@
 //! - `clap`: clap attributes of the argument, responsible for the path to the configuration file
 //!
 //! **Note:** in this case, clap attribute must have the nested `long` attribute (`clap(long = "...")`)
-//! - `default`: default configuration file path
-//! - `optional`: If the attribute is set, macro won't return Error if file is not found at set (by `clap`/`env`/`default`) path. Does not take values.\
+//! - `default`: default configuration file path or a list of paths (`default = ["./app.toml", "$XDG_CONFIG_HOME/app/app.toml"]`).
+//!   The paths are checked in order and the first existing file is loaded. `~` and environment variables (`$VAR` or `${VAR}`)
+//!   are expanded, paths with unset variables are skipped.
+//!   If none of the files exists, the error lists the checked and the skipped paths.
+//! - `optional`: If the attribute is set, macro won't return Error if the path is not set (by `clap`/`env`/`default`)
+//!   or none of the `default` files exists. Does not take values.\
+//! - `multiple`: The path can be set several times in the command line (`--config base.toml --config site.toml`)
+//!   and as a list in the environment variable, separated the same way as `PATH` (`base.toml:site.toml` on Unix).
+//!   All the files are loaded in the given order, so the later ones take precedence. Does not take values.\
+//! - `dir`: The path (set by `clap`/`env`/`default`) is a directory with drop-in configuration files (like `/etc/app/conf.d`).
+//!   All the files of the directory are loaded in lexical order, their formats are detected by the extensions,
+//!   so `format` must not be set. If `optional` is set, a missing directory is skipped. Does not take values.
+//! - `glob`: Pattern of the names of the files to load from the `dir` (like `"*.toml"`).
+//!   If not set, all the files with known extensions are loaded.\
+//! - `discover`: Look for the configuration file in the standard places if the path is not set by `clap`/`env`.
+//!   The files `./<name>.<ext>`, `<dir>/<name>/<name>.<ext>` and `<dir>/<name>.<ext>` are checked in order,
+//!   where `dir` is `$XDG_CONFIG_HOME` (`~/.config` by default) and then each of `$XDG_CONFIG_DIRS` (`/etc/xdg` by default).
+//!   `name` is the binary file name (`discover`) or the given one (`discover = "app"`).
+//!   If `format` is not set, any known extension is accepted and the format is detected by it.
+//!   The found file can be obtained by the [`config_files`](#config-files) field.\
+//! - `search_upwards`: Name of the file (like `search_upwards = ".apprc.toml"`) to look for in the current working directory
+//!   and all its parents up to the filesystem root, the way cargo finds `Cargo.toml`, if the path is not set by `clap`/`env`.
+//!   The nearest file is loaded or, if `multiple` is set, all the found files are loaded so the nearer ones take precedence.
+//!   If `format` is not set, it is detected by the file extension. Found paths are absolute,
+//!   so the directory of the file is the parent of the path obtained by the [`config_files`](#config-files) field.\
 //!
 //! **Note:** It is allowed to specify multiple files: all of them will be merged.
-//!     If there is a collision (the values of a particular key have been specified in two or more files),
+//!     Tables are merged recursively. If there is a collision (the values of a particular key have been specified in two or more files),
 //!     the value will be assigned from the file that has been described later (in the attribute list).
+//! So drop-in directories should be described after the main configuration file:
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(
+//!     file(format = "toml", default = "/etc/app/app.toml"),
+//!     file(dir, glob = "*.toml", env = "app_conf_dir", default = "/etc/app/conf.d", optional)
+//! )]
+//! struct AppConfig {
+//!     #[source(config)]
+//!     port: u16,
+//! }
+//! ```
+//!
+//! **Note:** The reserved `include` key of a configuration file (`include = ["common.toml", "secrets.toml"]`)
+//! lists the files to load along with it. Relative paths are resolved against the directory of the including file,
+//! the format is detected by the extension (the format of the including file is used if it is unknown).
+//! The included files are merged before the including one (tables are merged recursively), so the including file wins.
+//! Included files can include others,
+//! cyclic includes are reported as errors.
 //!
 //! **Example**
 //! ```
@@ -175,6 +238,62 @@
 //! - variable `iter` from configuration file with path set by the `demo_config` environment variable
 //! - default value (`5`)
 //!
+//! ### `dotenv`
+//! Dotenv file to take environment variables from (useful for the local development).
+//! The variables of the file are added to the environment the `env` sources are searched in,
+//! but the real environment variables take precedence. The process environment is not modified.
+//! Can be set in the short form `dotenv = ".env"` (the file is optional) or
+//! with the same nested attributes as the [file](#file): `clap`, `env`, `default` and `optional`.
+//!
+//! The file consists of `KEY=value` lines. `export` prefix, comments (`# ...`),
+//! single-quoted (`'...'`, taken literally) and double-quoted (`"..."`, with escapes like `\n`) values are supported.
+//! Quoted values can take several lines.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(dotenv(clap(long = "env-file"), env = "app_env_file", default = ".env", optional))]
+//! struct AppConfig {
+//!     #[source(env = "database_url")]
+//!     database_url: String,
+//! }
+//! ```
+//!
+//! ### `profiles`
+//! Profiles of the configuration: variants like `dev`, `staging` and `prod` kept in one file.
+//! The selected `[profile.<name>]` table is deep-merged over the root table of the configuration files
+//! (nested tables are merged, other values of the profile replace the root ones).
+//! The name of the profile is taken by the nested attributes `clap`, `env` and `default` (checked in that order, like the [file](#file) path).
+//! Unknown profile name selected by `clap` or `env` is an error, missing `default` profile is skipped.
+//! The `profile` table itself can't be used as a field value if the attribute is set.
+//! The applied profile can be obtained by the [`config_profile`](#config-profile) field.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(
+//!     file(format = "toml", default = "./config.toml"),
+//!     profiles(env = "app_profile", clap(long = "profile"), default = "dev")
+//! )]
+//! struct AppConfig {
+//!     #[source(config = "server.port")]
+//!     port: u16,
+//! }
+//! ```
+//! ```toml
+//! [server]
+//! host = "localhost"
+//! port = 8080
+//!
+//! [profile.dev]
+//!
+//! [profile.prod.server]
+//! port = 443
+//! ```
+//! With `--profile prod` the `port` is `443`, the `server.host` stays `localhost`.
+//!
 //! ### `clap`
 //! Clap app attributes, like `name`, `version`, etc.
 //! Full list of supported clap attributes can be checked in the [Appendix](#clap-command)
@@ -223,6 +342,151 @@
 //! 2. environment variables
 //! 3. configuration files
 //!
+//! ### `interpolate`
+//! Values of the fields are expanded before deserialization: `${VAR}` is replaced with the value of the `VAR` environment variable,
+//! `${VAR:-default}` takes `default` if the variable is not set or is empty (`${VAR-default}` — only if it is not set),
+//! and `$$` is the escaped `$`. Defaults can contain variables too: `${HOST:-${FALLBACK_HOST}}`.
+//! With `interpolate(config)` the variables are also looked up in the configuration files by the dotted key path
+//! (e.g. `${db.host}`), environment variables take precedence.
+//!
+//! Only the values of the configuration files and the environment variables are expanded, the values of the other sources
+//! (command line, secret files, command outputs, etc.) are taken as is.
+//! Only the strings of the configuration file values are expanded. Values of the environment variables are inserted as is,
+//! values of the configuration keys are expanded recursively.
+//! Cyclic references and undefined variables without a default are reported as errors.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(file(format = "toml", default = "./config.toml"), interpolate)]
+//! struct Config {
+//!     #[source(config)]
+//!     url: String,
+//! }
+//! ```
+//! `url = "postgres://${DB_HOST}:${DB_PORT:-5432}/app"` of the "config.toml" will be expanded with the `DB_HOST`
+//! and the `DB_PORT` environment variables.
+//!
+//! The attribute can also be set for a single field: `#[source(env, config, interpolate)]`,
+//! and for the [flatten](#flatten) structures: `#[interpolate]`.
+//!
+//! ### `lenient`
+//! Values of the boolean and integer fields (of `T` for `Option<T>`) taken from the environment variables and the command line
+//! are parsed according to the common shell conventions:
+//! - booleans: `yes`/`no`, `y`/`n`, `on`/`off`, `1`/`0` and `true`/`false` in any case,
+//! - integers: underscores (`1_000`), `0x`, `0o` and `0b` prefixes,
+//! - surrounding whitespaces are ignored.
+//!
+//! Values of the configuration files and the other sources are parsed as usual. Does not take values.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(env_prefix = "app", lenient)]
+//! struct Config {
+//!     #[source(env, default = false)]
+//!     debug: bool,
+//!     #[source(clap(long), env, default = 1)]
+//!     workers: u32,
+//! }
+//! ```
+//! `APP_DEBUG=yes ./my_app --workers 0x10` sets `debug` to `true` and `workers` to `16`.
+//!
+//! The attribute can also be set for a single field: `#[source(env, lenient)]`,
+//! and for the [flatten](#flatten) structures: `#[lenient]`.
+//!
+//! ### `deny_unknown_keys`
+//! Keys of the configuration files that are not consumed by any field (including the [flatten](#flatten) ones) are reported as an error
+//! with "did you mean" suggestions, so typos like `prediction_dealy` are not silently ignored.
+//! The keys are compared by their full paths along with the [table](#table), a consumed table (e.g. by a `HashMap` field)
+//! consumes all its nested keys. Does not take values.
+//! Can be enabled or disabled at runtime by `ConfigOption::DenyUnknownKeys`, which takes precedence over the attribute.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(file(format = "toml", default = "./config.toml"), deny_unknown_keys)]
+//! struct Config {
+//!     #[source(config)]
+//!     prediction_delay: u32,
+//! }
+//! ```
+//!
+//! ### `unused_env`
+//! Environment variables with the [env_prefix](#env_prefix) that are not consumed by any field (including the [flatten](#flatten) ones),
+//! like `APP_PREDICTON_DELAY`, are returned as an error with "did you mean" suggestions (`unused_env = "deny"`)
+//! or collected without an error (`unused_env = "warn"`), so the application can report them the way it likes.
+//! Names of the collected variables can be obtained by the [`unused_env`](#unused-env) field.
+//! Variables with the paths of the configuration files, of the dotenv file and with the profile are considered consumed.
+//! The check is skipped if the prefix is empty.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(env_prefix = "app", unused_env = "deny")]
+//! struct Config {
+//!     #[source(env)]
+//!     prediction_delay: u32,
+//! }
+//! ```
+//!
+//! ### `validate`
+//! Function checking the rules that span several fields (like `min <= max`).
+//! It is called with the initialized structure and must have the `fn(&Self) -> Result<(), impl std::fmt::Display>` signature.
+//! The error is returned as `Error::StructValidationFailed`.
+//!
+//! Flatten structures take the function with the `#[validate(function)]` attribute.
+//! The error of the nested structure (as well as the error of the field [validation](#validate-1)) contains the path
+//! of the flatten fields, like `tls` or `limits.port`.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::{config, Flatten};
+//! # use serde::Deserialize;
+//! #[config(env_prefix = "app", validate = Self::check)]
+//! struct Config {
+//!     #[source(env, default = 1)]
+//!     min: u32,
+//!     #[source(env, default = 10)]
+//!     max: u32,
+//!     #[flatten]
+//!     tls: Tls,
+//! }
+//!
+//! impl Config {
+//!     fn check(&self) -> Result<(), String> {
+//!         if self.min > self.max {
+//!             Err(format!("min ({}) is greater than max ({})", self.min, self.max))
+//!         } else {
+//!             Ok(())
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(Deserialize, Flatten)]
+//! #[validate(Self::check)]
+//! struct Tls {
+//!     #[source(env, default)]
+//!     tls: bool,
+//!     #[source(env, default)]
+//!     tls_cert: Option<String>,
+//! }
+//!
+//! impl Tls {
+//!     fn check(&self) -> Result<(), &'static str> {
+//!         if self.tls && self.tls_cert.is_none() {
+//!             Err("tls_cert is required when tls is enabled")
+//!         } else {
+//!             Ok(())
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! ## Field attributes
 //! Only fields can be annotated with the following attributes and only one of them can be assigned to a field.
 //!
@@ -283,6 +547,87 @@
 //! Field `rate` will be searched in the "input.data.images" table of the "config.toml"
 //! configuration file by the `frame_rate` key.
 //!
+//! **Note:** If the field is also annotated with `relative_to_file` (`#[source(config, relative_to_file)]`),
+//! a relative path from the configuration file is resolved against the directory of the file that declared it,
+//! so `cert = "certs/server.pem"` in `/etc/app/app.toml` becomes `/etc/app/certs/server.pem`.
+//! Values from the command line and the environment are kept as is (i.e. relative to the current working directory).
+//!
+//! #### `env_file`
+//! The name of the environment variable (without the `_file` suffix) that contains the path to the file with the value,
+//! like Docker and Kubernetes deliver secrets. The naming is the same as for the [env](#env) source,
+//! so `#[source(env_file)]` of the `db_password` field with `env_prefix = "app"` reads the file set by `APP_DB_PASSWORD_FILE`.
+//! The trailing newline of the file is trimmed. If the variable is set, but the file can't be read, an error is returned.
+//!
+//! #### `secret_file`
+//! The name of the file in the secrets directory to take the value from. If the attribute is set without a value,
+//! the name of the file is the field name. The secrets directory is `/run/secrets` by default and can be changed
+//! with the `secrets_dir = "..."` structure attribute (it is also allowed for the [flatten](#flatten) structures).
+//! The trailing newline of the file is trimmed, missing file is skipped.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(env_prefix = "app", secrets_dir = "/run/secrets")]
+//! struct AppConfig {
+//!     // APP_DB_PASSWORD, then file set by APP_DB_PASSWORD_FILE, then /run/secrets/db_password
+//!     #[source(env, env_file, secret_file)]
+//!     db_password: String,
+//! }
+//! ```
+//!
+//! #### `exec`
+//! Command to take the value from its output, like `pass show db/password` or a local credential helper.
+//! The command is run without a shell: `exec = ["program", "arg", ...]`.
+//! Its stdout with trimmed whitespaces is used as the value. The timeout is 10 seconds by default and
+//! can be set in seconds with the extended form: `exec(command = [...], timeout = 3)`.
+//! If the command can't be started, exits with a failure status or times out, the
+//! [ExecFailed](../enum.Error.html#variant.ExecFailed) error is returned.
+//! The command is run only if the value is not found in the previous sources.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config]
+//! struct AppConfig {
+//!     #[source(env = "db_password", exec = ["pass", "show", "db/password"])]
+//!     db_password: String,
+//! }
+//! ```
+//!
+//! #### `custom`
+//! Custom source of the value, like SQLite, local KV store or a test fake: `custom(path = SourceType, key = "...")`.
+//! `SourceType` must implement the [ValueSource](../trait.ValueSource.html) trait and its instance must be registered
+//! with the [ConfigOption::CustomSource](../enum.ConfigOption.html#variant.CustomSource) option,
+//! otherwise an error is returned. If `key` is not set, the field name is used as the key.
+//! The source can return a raw string (deserialized like the environment values)
+//! or a structured value (deserialized like the configuration file values).
+//!
+//! **Example**
+//! ```
+//! use std::{collections::HashSet, sync::Arc};
+//! use config_manager::{config, ConfigInit, ConfigOption, Error, SourceValue, ValueSource};
+//!
+//! struct Vault;
+//!
+//! impl ValueSource for Vault {
+//!     fn get(&self, key: &str) -> Result<Option<SourceValue>, Error> {
+//!         Ok((key == "db/password").then(|| "secret".into()))
+//!     }
+//! }
+//!
+//! #[config]
+//! struct AppConfig {
+//!     #[source(env = "db_password", custom(path = Vault, key = "db/password"))]
+//!     db_password: String,
+//! }
+//!
+//! fn init() -> Result<AppConfig, Error> {
+//!     AppConfig::parse_options(HashSet::from([ConfigOption::CustomSource(Arc::new(Vault))]))
+//! }
+//! ```
+//!
 //! #### `clap`
 //! Clap-crate field attributes, like `long`, `short`. Full list of supported clap attributes can be checked in the [Appendix](#clap-arg).
 //!
@@ -293,6 +638,14 @@
 //! - `help`: will be taken from doc comments of the field (aka `///` or `/** */`),
 //! - `long_help`: will be taken from doc comments of the field (aka `///` or `/** */`),
 //!
+//! **Note:** values of the arguments are checked by clap according to the field type (of `T` for `Option<T>`):
+//! `clap::ValueEnum` types take only their possible values (that are shown in the help),
+//! enums deserialized by serde from unit variants take their serde names (`rename`, `rename_all` are honoured),
+//! values of the primitive types (numbers, `bool`, `char`) must be parsed by `FromStr`.
+//! The check is skipped for the `secret`, [lenient](#lenient) fields and the fields with `deserialize_with`.
+//! The values of such enums taken from the other sources are reported as `expected one of ...` on failure.
+//! Value name of the argument is the field name in upper case (like `--port <PORT>`) unless `value_name` is set.
+//!
 //! **Note:** `#[source(clap)]` is equivalent to `#[source(clap(long))]` \
 //! **Note:** boolean fields can be marked as `#[source(clap(flag))]` that allow to set it as `true` with no value provided. \
 //! **Example:** the following field can be set to `true` using the CLI: `./my_app -f` or `./my_asp --flag true`.
@@ -306,6 +659,32 @@
 //! }
 //! ```
 //!
+//! **Note:** flags can be marked as `negatable`: `--no-<long>` argument is generated that sets the field to `false`.
+//! The last of `--<long>` and `--no-<long>` wins. \
+//! **Example:** `true` taken from the configuration file can be switched off using the CLI: `./my_app --no-feature`.
+//! ```
+//! # use config_manager::config;
+//!
+//! #[config(file(format = "toml", default = "./config.toml", optional))]
+//! struct Cfg {
+//!     #[source(clap(long, flag, negatable), config)]
+//!     feature: bool
+//! }
+//! ```
+//!
+//! **Note:** integer fields can be marked as `#[source(clap(count))]`: the value is the number of occurrences of the argument.
+//! If the argument is not passed, the value is taken from the next sources. \
+//! **Example:** `./my_app -vvv` sets `verbose` to `3`, `./my_app` takes it from the `APP_VERBOSE` environment variable or the default.
+//! ```
+//! # use config_manager::config;
+//!
+//! #[config(env_prefix = "app")]
+//! struct Cfg {
+//!     #[source(clap(short, count), env, default = 0)]
+//!     verbose: u8
+//! }
+//! ```
+//!
 //! #### `deserialize_with`
 //! Custom deserialization of the field. The deserialization function should have the following signature:
 //! ```ignore
@@ -335,6 +714,86 @@
 //! }
 //! ```
 //!
+//! #### `validate`
+//! Rules that the deserialized value must satisfy (the default value is checked too):
+//! - `range = <range>`: the value is contained in the range (`1..=65535`, `..10`, and so on).
+//! - `min_len = <n>`, `max_len = <n>`: bounds of the length (in chars for strings) of strings, paths and collections.
+//! - `regex = "<pattern>"`: the string value matches the regular expression. Invalid patterns are reported at compile time.
+//! - `with = <function>`: custom check with the `fn(&FieldType) -> Result<(), impl std::fmt::Display>` signature.
+//!
+//! The rules of the `Option<T>` field are checked against the `T` value (`with` takes `&T`), `None` is always valid.
+//!
+//! Several rules can be set at once. The violation is reported as `Error::ValidationFailed`
+//! with the name of the field, the source of the value and the violated rule.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #[config(env_prefix = "app")]
+//! struct Server {
+//!     #[source(env, default = 8080, validate(range = 1..=65535))]
+//!     port: u16,
+//!     #[source(env, default = "info", validate(regex = "^(debug|info|warn|error)$"))]
+//!     log_level: String,
+//!     #[source(env, validate(min_len = 1, with = not_root))]
+//!     user: String,
+//! }
+//!
+//! fn not_root(user: &String) -> Result<(), String> {
+//!     if user == "root" {
+//!         Err("root is not allowed".to_string())
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! #### Constraints
+//! Constraints between the fields are checked over the resolved values, whatever sources supplied them
+//! (unlike the `requires` and `conflicts_with` clap attributes that affect only the command line arguments).
+//! The field is considered supplied if its value is taken from any source but the default.
+//! - `required_if(field = "other", eq = <value>)`: the field must be supplied if the value of the `other` field equals to `value`
+//!   (so the field usually has a default).
+//! - `conflicts_with = "other"`: the field and the `other` field can't be supplied at the same time.
+//!
+//! The referenced field must be a field of the same structure with the [source](#source) attribute.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #[config(env_prefix = "app", file(format = "toml", default = "./config.toml", optional))]
+//! struct Config {
+//!     #[source(env, config, default = "single")]
+//!     mode: String,
+//!     #[source(env, config, default, required_if(field = "mode", eq = "cluster"))]
+//!     seeds: Vec<String>,
+//!     #[source(env, config, default, conflicts_with = "password_file")]
+//!     password: String,
+//!     #[source(env, config, default)]
+//!     password_file: String,
+//! }
+//! ```
+//!
+//! #### `secret`
+//! The value of the field (like a password) is masked in the generated error messages,
+//! and the default and possible values of the `clap` argument are hidden in the help.
+//! Details of the deserialization errors and the errors of the `with` [validation](#validate-1) functions are omitted.
+//!
+//! The field attribute doesn't affect the `Debug` output of the structure:
+//! use the `Secret<T>` wrapper to redact it. `Secret<T>` is deserialized as `T`, the value is accessible with the `expose` method.
+//!
+//! **Example**
+//! ```
+//! # use config_manager::{config, Secret};
+//! #[config(env_prefix = "app")]
+//! struct Config {
+//!     #[source(env, secret)]
+//!     db_password: String,
+//!     #[source(env, secret)]
+//!     api_token: Secret<String>,
+//! }
+//! ```
+//!
 //! ### Flatten
 //! If a field is annotated with the `flatten` attribute, it will be parsed as a nested structure and its fields will be initiated
 //! like fields of the primary config. In this case, the field's type must implement `config_manager::Flatten`
@@ -364,7 +823,7 @@
 //! - `env_prefix` will be inherited from the initial struct
 //!
 //! #### Flatten attributes
-//! Flatten struct may have the following helper attributes: `table`, `flatten`, `source` (they work the same way as the described above ones).
+//! Flatten struct may have the following helper attributes: `table`, `secrets_dir`, `interpolate`, `lenient`, `validate`, `flatten`, `source` (they work the same way as the described above ones).
 //! ### Subcommand
 //! If a field is annotated with the `subcommand` attribute, it will be taken as a `clap` subcommand
 //! (see [clap documentation](https://docs.rs/clap/latest/clap/_derive/_tutorial/index.html#subcommands) for more info).
@@ -401,11 +860,67 @@
 //! ```
 //! **Notes:**
 //! - Value for the `subcommand` enumeration will be searched only in command line, so the `source` and the `flatten` attributes are forbidden
-//!     (flatten `subcommand` attribute is allowed due to clap documentation).
+//!   (flatten `subcommand` attribute is allowed due to clap documentation).
 //! - Multiple `subcommand` fields are forbidden.
 //! - `subcommand` field in nested(`flatten`) structures are forbidden.
 //! - `subcommand` field can be optional (`Option<T>`, `T: clap::Subcommand + serde::Deserialize`),
-//!     so if no subcommand is found in the command line, the `None` will be assigned.
+//!   so if no subcommand is found in the command line, the `None` will be assigned.
+//!
+//! ### Config files
+//! If a field is annotated with the `config_files` attribute, it will be assigned with the paths of
+//! the configuration files that have been loaded (in the loading order).
+//! The field's type must be constructible from an iterator of [PathBuf](std::path::PathBuf) (like `Vec<PathBuf>`).
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! # use std::path::PathBuf;
+//! #
+//! #[config(file(discover = "app", optional))]
+//! struct Config {
+//!     #[config_files]
+//!     files: Vec<PathBuf>,
+//! }
+//! ```
+//!
+//! ### Config profile
+//! If a field is annotated with the `config_profile` attribute, it will be assigned with the name of the [profile](#profiles)
+//! that has been applied, or `None` if there is no such one.
+//! The field's type must be `Option<T>`, where `T` is constructible from `&str` (like `Option<String>`).
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(
+//!     file(format = "toml", default = "./config.toml", optional),
+//!     profiles(env = "app_profile", default = "dev")
+//! )]
+//! struct Config {
+//!     #[config_profile]
+//!     profile: Option<String>,
+//! }
+//! ```
+//!
+//! ### Unused env
+//! If a field is annotated with the `unused_env` attribute, it will be assigned with the names of the environment variables
+//! with the [env_prefix](#env_prefix) that are not consumed by any field (see [unused_env](#unused_env)).
+//! The variables are collected even if the `unused_env` attribute of the structure is not set.
+//! Only the top-level structure can have such a field.
+//! The field's type must be constructible from an iterator of `String` (like `Vec<String>`).
+//!
+//! **Example**
+//! ```
+//! # use config_manager::config;
+//! #
+//! #[config(env_prefix = "app", unused_env = "warn")]
+//! struct Config {
+//!     #[source(env)]
+//!     prediction_delay: u32,
+//!     #[unused_env]
+//!     unused: Vec<String>,
+//! }
+//! ```
 //!
 //! ## get_command
 //! [ConfigInit](../trait.ConfigInit.html) trait has the [get_command](../trait.ConfigInit.html#tymethod.get_command)
@@ -464,4 +979,4 @@
 //! Documentation on original methods: [clap::Arg]
 //!
 //! Next attributes are allowed: \
-//! help, long_help, short, long, flag, help_heading, alias, short_alias, aliases, short_aliases, visible_alias, visible_short_alias, visible_aliases, visible_short_aliases, index, last, requires, exclusive, value_name, value_hint, ignore_case, allow_hyphen_values, allow_negative_numbers, require_equals, display_order, next_line_help, hide, hide_possible_values, hide_default_value, hide_short_help, hide_long_help, conflicts_with, conflicts_with_all, overrides_with, overrides_with_all,
+//! help, long_help, short, long, flag, negatable, count, help_heading, alias, short_alias, aliases, short_aliases, visible_alias, visible_short_alias, visible_aliases, visible_short_aliases, index, last, requires, exclusive, value_name, value_hint, ignore_case, allow_hyphen_values, allow_negative_numbers, require_equals, display_order, next_line_help, hide, hide_possible_values, hide_default_value, hide_short_help, hide_long_help, conflicts_with, conflicts_with_all, overrides_with, overrides_with_all,
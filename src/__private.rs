@@ -12,13 +12,21 @@ pub use clap;
 pub use config;
 pub use ctor;
 pub use deser_hjson;
+pub use regex;
 pub use serde;
 pub use serde_json;
 
 pub use config_manager_proc::__Config__;
 
 use super::utils;
-use std::collections::HashMap;
+use crate::{SourceValue, ValueSource};
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
 
 pub struct EnvData {
     inner: HashMap<String, String>,
@@ -32,13 +40,269 @@ impl EnvData {
     pub fn from(inner: HashMap<String, String>) -> Self {
         Self { inner }
     }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &String> {
+        self.inner.keys()
+    }
+}
+
+pub struct ConfigFileData {
+    pub(crate) values: HashMap<String, config::Value>,
+    pub(crate) files: Vec<PathBuf>,
+    /// Files that the top-level keys were taken from
+    pub(crate) origins: HashMap<String, PathBuf>,
+    /// Profile whose table has been merged over the root one
+    pub(crate) profile: Option<String>,
+}
+
+impl ConfigFileData {
+    pub fn values(&self) -> &HashMap<String, config::Value> {
+        &self.values
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+}
+
+pub struct CustomSources {
+    inner: Vec<Arc<dyn ValueSource>>,
+}
+
+impl CustomSources {
+    pub fn from(inner: Vec<Arc<dyn ValueSource>>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get<T: ValueSource>(&self, key: &str) -> Result<Option<String>, crate::Error> {
+        let source = self
+            .inner
+            .iter()
+            .find(|source| Any::type_id(&***source) == std::any::TypeId::of::<T>())
+            .ok_or_else(|| {
+                crate::Error::ExternalError(format!(
+                    "value source {} is not registered, use ConfigOption::CustomSource to register it",
+                    std::any::type_name::<T>()
+                ))
+            })?;
+
+        Ok(match source.get(key)? {
+            None => None,
+            Some(SourceValue::Raw(value)) => Some(value),
+            Some(SourceValue::Structured(value)) => Some(value.to_string()),
+        })
+    }
+}
+
+/// Length of the value for the `min_len`/`max_len` validation rules.
+pub trait ValueLen {
+    fn value_len(&self) -> usize;
+}
+
+impl ValueLen for String {
+    fn value_len(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl ValueLen for PathBuf {
+    fn value_len(&self) -> usize {
+        self.as_os_str().len()
+    }
+}
+
+macro_rules! impl_value_len {
+    ($($ty:ident<$($param:ident),+>),* $(,)?) => {
+        $(
+            impl<$($param),+> ValueLen for $ty<$($param),+> {
+                fn value_len(&self) -> usize {
+                    self.len()
+                }
+            }
+        )*
+    };
+}
+
+impl_value_len!(
+    Vec<T>,
+    VecDeque<T>,
+    HashMap<K, V>,
+    HashSet<T>,
+    BTreeMap<K, V>,
+    BTreeSet<T>,
+);
+
+/// Selects the value parser of the clap argument by the type of the field (with the autoref specialization):
+/// possible values of `clap::ValueEnum` or of the enumeration with unit variants,
+/// validation with `FromStr` (of the primitive types only) or any string.
+///
+/// Values are kept as strings to be deserialized like the values of the other sources.
+/// Null and empty values of the optional fields are accepted.
+pub struct ValueParserProbe<T> {
+    optional: bool,
+    ty: PhantomData<T>,
+}
+
+// Derive would require `T: Copy`
+impl<T> Clone for ValueParserProbe<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ValueParserProbe<T> {}
+
+impl<T> ValueParserProbe<T> {
+    pub fn new(optional: bool) -> Self {
+        Self {
+            optional,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T> ValueParserProbe<T> {
+    fn possible_values(
+        self,
+        values: impl IntoIterator<Item = clap::builder::PossibleValue>,
+    ) -> clap::builder::ValueParser {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        if self.optional {
+            values.extend(
+                ["", "null"].map(|value| clap::builder::PossibleValue::new(value).hide(true)),
+            );
+        }
+        clap::builder::ValueParser::new(clap::builder::PossibleValuesParser::new(values))
+    }
+
+    /// Possible values of the enumeration with unit variants (see [unit_variants]).
+    fn unit_variants(self) -> Option<clap::builder::ValueParser>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        utils::unit_variants::<T>()
+            .map(|variants| self.possible_values(variants.into_iter().map(Into::into)))
+    }
+}
+
+pub trait ValueEnumParser {
+    fn value_parser(self) -> clap::builder::ValueParser;
+}
+
+impl<T: clap::ValueEnum + Clone + Send + Sync + 'static> ValueEnumParser
+    for &&&ValueParserProbe<T>
+{
+    fn value_parser(self) -> clap::builder::ValueParser {
+        self.possible_values(
+            T::value_variants()
+                .iter()
+                .filter_map(clap::ValueEnum::to_possible_value),
+        )
+    }
+}
+
+/// Types whose `FromStr` accepts the same values as their deserialization from the string,
+/// so the value of the argument can be checked by `FromStr`.
+/// `FromStr` of the other types (like `serde_json::Value`) may reject the values the deserialization accepts.
+pub trait FromStrValue: serde::de::DeserializeOwned + FromStr + 'static
+where
+    Self::Err: Display,
+{
+}
+
+macro_rules! impl_from_str_value {
+    ($($ty:ty),* $(,)?) => {
+        $(impl FromStrValue for $ty {})*
+    };
+}
+
+impl_from_str_value!(
+    bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize,
+);
+
+pub trait FromStrParser {
+    fn value_parser(self) -> clap::builder::ValueParser;
+}
+
+impl<T> FromStrParser for &&ValueParserProbe<T>
+where
+    T: FromStrValue,
+    T::Err: Display,
+{
+    fn value_parser(self) -> clap::builder::ValueParser {
+        if let Some(parser) = self.unit_variants() {
+            return parser;
+        }
+        let optional = self.optional;
+        clap::builder::ValueParser::new(move |value: &str| -> Result<String, String> {
+            if !(optional && utils::is_null_or_empty(value)) {
+                value.parse::<T>().map_err(|err| err.to_string())?;
+            }
+            Ok(value.to_string())
+        })
+    }
+}
+
+pub trait DeserializeParser {
+    fn value_parser(self) -> clap::builder::ValueParser;
+}
+
+impl<T: serde::de::DeserializeOwned> DeserializeParser for &ValueParserProbe<T> {
+    fn value_parser(self) -> clap::builder::ValueParser {
+        self.unit_variants()
+            .unwrap_or_else(clap::builder::ValueParser::string)
+    }
+}
+
+pub trait StringParser {
+    fn value_parser(self) -> clap::builder::ValueParser;
+}
+
+impl<T> StringParser for ValueParserProbe<T> {
+    fn value_parser(self) -> clap::builder::ValueParser {
+        clap::builder::ValueParser::string()
+    }
+}
+
+/// Serialized names of the variants if `T` is an enumeration with unit variants only.
+pub fn unit_variants<T: serde::de::DeserializeOwned>() -> Option<Vec<&'static str>> {
+    utils::unit_variants::<T>()
+}
+
+pub fn is_null_or_empty(value: &str) -> bool {
+    utils::is_null_or_empty(value)
+}
+
+pub fn lenient_bool(value: String) -> String {
+    utils::lenient_bool(value)
+}
+
+pub fn lenient_int(value: String) -> String {
+    utils::lenient_int(value)
+}
+
+pub fn redact_error(err: crate::Error, secret: &str) -> crate::Error {
+    utils::redact_error(err, secret)
+}
+
+pub fn nest_error(err: crate::Error, field: &str) -> crate::Error {
+    utils::nest_error(err, field)
 }
 
 pub trait Flatten {
     fn get_args() -> Vec<clap::Arg>;
+    /// Full paths of the configuration file keys the structure consumes.
+    fn config_keys() -> Vec<String>;
+    /// Names of the environment variables the structure consumes.
+    fn env_keys(env_prefix: Option<String>) -> Result<Vec<String>, super::Error>;
     fn parse(
         env_data: &EnvData,
-        config_file_data: &HashMap<String, config::Value>,
+        config_file_data: &ConfigFileData,
+        custom_sources: &CustomSources,
         clap_data: &clap::ArgMatches,
         env_prefix: Option<String>,
     ) -> Result<Self, super::Error>
@@ -63,3 +327,95 @@ pub fn find_field_in_table(
 ) -> Result<Option<String>, crate::Error> {
     utils::find_field_in_table(config, table, field_name)
 }
+
+pub fn extend_with_dotenv(
+    env: &mut HashMap<String, String>,
+    path: &str,
+    optional: bool,
+) -> Result<(), crate::Error> {
+    utils::extend_with_dotenv(env, path, optional)
+}
+
+pub fn exec_command(
+    command: &[&str],
+    timeout: std::time::Duration,
+) -> Result<Option<String>, crate::Error> {
+    utils::exec_command(command, timeout).map(Some)
+}
+
+pub fn interpolate(
+    value: String,
+    json: bool,
+    env_data: &EnvData,
+    config: Option<&ConfigFileData>,
+    field: &str,
+) -> Result<String, crate::Error> {
+    utils::interpolate(value, json, env_data, config, field)
+}
+
+pub fn read_env_file(env_data: &EnvData, key: &str) -> Result<Option<String>, crate::Error> {
+    utils::read_env_file(env_data, key)
+}
+
+pub fn read_secret_file(dir: &str, name: &str) -> Result<Option<String>, crate::Error> {
+    utils::read_secret_file(dir, name)
+}
+
+pub fn find_path_in_table(
+    config: &ConfigFileData,
+    table: Option<String>,
+    field_name: String,
+) -> Result<Option<String>, crate::Error> {
+    utils::find_path_in_table(config, table, field_name)
+}
+
+pub fn apply_profile(
+    data: &mut ConfigFileData,
+    selected: Option<&str>,
+    default: Option<&str>,
+) -> Result<(), crate::Error> {
+    utils::apply_profile(data, selected, default)
+}
+
+pub fn check_unknown_keys(data: &ConfigFileData, consumed: &[String]) -> Result<(), crate::Error> {
+    utils::check_unknown_keys(data, consumed)
+}
+
+pub fn check_unused_env(
+    env_data: &EnvData,
+    prefix: &str,
+    consumed: &[String],
+    deny: bool,
+) -> Result<Vec<String>, crate::Error> {
+    utils::check_unused_env(env_data, prefix, consumed, deny)
+}
+
+pub fn collect_config_files(
+    files: Vec<crate::FileOptions>,
+) -> Result<ConfigFileData, crate::Error> {
+    utils::collect_config_files(files)
+}
+
+pub fn detect_format(path: &str) -> Result<config::FileFormat, crate::Error> {
+    utils::detect_format(path)
+}
+
+pub fn discover_config_file(
+    name: &str,
+    format: Option<config::FileFormat>,
+    env_data: &EnvData,
+) -> Option<crate::FileOptions> {
+    utils::discover_config_file(name, format, env_data)
+}
+
+pub fn search_upwards(name: &str, all: bool) -> Result<Vec<String>, crate::Error> {
+    utils::search_upwards(name, all)
+}
+
+pub fn find_default_path(
+    candidates: &[&str],
+    env_data: &EnvData,
+    optional: bool,
+) -> Result<Option<String>, crate::Error> {
+    utils::find_default_path(candidates, env_data, optional)
+}
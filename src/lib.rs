@@ -97,9 +97,11 @@
 //! }
 //! ```
 
+use std::any::Any;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
+use std::sync::Arc;
 
 pub use config_manager_proc::config;
 pub use config_manager_proc::Flatten;
@@ -111,10 +113,34 @@ mod utils;
 
 /// Runtime initializing error.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     MissingArgument(String),
     FailedParse(String),
     ExternalError(String),
+    /// Command of the `exec` source failed.
+    ExecFailed {
+        /// Program and its arguments.
+        command: Vec<String>,
+        /// Reason of the failure (like exit status and stderr of the command).
+        message: String,
+    },
+    /// Value of the field violates the validation rule.
+    ValidationFailed {
+        /// Name of the field (dotted path for the fields of the flatten structures).
+        field: String,
+        /// Source the value was taken from (like `env` or `default`).
+        source: String,
+        /// Violated rule and the details.
+        message: String,
+    },
+    /// Validation hook of the structure (`validate = ...` attribute) failed.
+    StructValidationFailed {
+        /// Dotted path of the flatten fields to the structure, empty for the top level structure.
+        path: String,
+        /// Error of the hook.
+        message: String,
+    },
 }
 
 impl fmt::Display for Error {
@@ -123,6 +149,26 @@ impl fmt::Display for Error {
             Error::MissingArgument(msg) | Error::FailedParse(msg) | Error::ExternalError(msg) => {
                 write!(f, "{}", msg)
             }
+            Error::ExecFailed { command, message } => {
+                write!(f, "command `{}` failed: {}", command.join(" "), message)
+            }
+            Error::ValidationFailed {
+                field,
+                source,
+                message,
+            } => {
+                write!(
+                    f,
+                    "invalid value of field {} taken from {}: {}",
+                    field, source, message
+                )
+            }
+            Error::StructValidationFailed { path, message } if path.is_empty() => {
+                write!(f, "invalid configuration: {}", message)
+            }
+            Error::StructValidationFailed { path, message } => {
+                write!(f, "invalid configuration of {}: {}", path, message)
+            }
         }
     }
 }
@@ -162,11 +208,98 @@ pub type FileFormat = config::FileFormat;
 ///
 /// **Note** Each option takes precedence over the corresponding structure attribute (see [cookbook](__cookbook/index.html) for more information).
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum ConfigOption {
     /// Prefix of the environment variables.
     EnvPrefix(String),
     /// Replacement of the usual source.
     ExplicitSource(Source),
+    /// Custom source of the values for the fields, annotated with `#[source(custom(path = SourceType))]`.
+    ///
+    /// Sources are distinguished by their types: only one source of each type can be registered.
+    CustomSource(Arc<dyn ValueSource>),
+    /// Whether keys of the configuration files that are not consumed by any field are an error
+    /// (see `#[config(deny_unknown_keys)]`).
+    DenyUnknownKeys(bool),
+}
+
+/// Custom source to find values for the fields (like SQLite, local KV store or a test fake).
+///
+/// The source is looked up by the field annotated with `#[source(custom(path = SourceType, key = "..."))]`
+/// and must be registered with the [ConfigOption::CustomSource] option.
+pub trait ValueSource: Any + Send + Sync {
+    /// Returns the value of the key or `None` if the key is not found (then the next source of the field is checked).
+    fn get(&self, key: &str) -> Result<Option<SourceValue>, Error>;
+}
+
+impl fmt::Debug for dyn ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ValueSource")
+    }
+}
+
+/// Value returned by the [ValueSource].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SourceValue {
+    /// Raw string that is deserialized like the values of the command line or the environment.
+    Raw(String),
+    /// Structured value that is deserialized like the values of the configuration files.
+    Structured(serde_json::Value),
+}
+
+impl From<String> for SourceValue {
+    fn from(value: String) -> Self {
+        Self::Raw(value)
+    }
+}
+
+impl From<&str> for SourceValue {
+    fn from(value: &str) -> Self {
+        Self::Raw(value.to_string())
+    }
+}
+
+impl From<serde_json::Value> for SourceValue {
+    fn from(value: serde_json::Value) -> Self {
+        Self::Structured(value)
+    }
+}
+
+/// Wrapper of the secret value (like a password or a token) that is redacted in the `Debug` output.
+///
+/// It is deserialized as the wrapped value, so it can be used as the type of the field.
+/// The value is accessible with the [Secret::expose] method only.
+#[derive(Clone, Default, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wraps the value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Reference to the wrapped value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(***)")
+    }
 }
 
 /// Replacement of the usual source to find values for the fields.
@@ -203,9 +336,23 @@ pub enum ClapSource {
 
 /// Description of the configuration file.
 #[derive(Debug, Clone)]
-pub struct FileOptions {
-    /// File format.
-    pub format: FileFormat,
-    /// Path to the file.
-    pub path: String,
+#[non_exhaustive]
+pub enum FileOptions {
+    /// Single configuration file.
+    File {
+        /// File format.
+        format: FileFormat,
+        /// Path to the file.
+        path: String,
+    },
+    /// Directory with drop-in configuration files (like `/etc/app/conf.d`).
+    ///
+    /// Files of the directory are loaded in lexical order, their formats are detected by the extensions.
+    Directory {
+        /// Path to the directory.
+        path: String,
+        /// Pattern of the names of the files to load (like `*.toml`).
+        /// If not set, all the files with known extensions are loaded.
+        glob: Option<String>,
+    },
 }
@@ -2,11 +2,35 @@
 // Copyright (c) 2022 JSRPC “Kryptonite”
 
 use std::{
+    any::Any,
     collections::HashMap,
     hash::{Hash, Hasher},
 };
 
-use crate::{ConfigOption, Source};
+use crate::{__private::ConfigFileData, ConfigOption, Source};
+
+mod dotenv;
+mod enum_variants;
+mod exec;
+mod files;
+mod interpolation;
+mod lenient;
+mod secret;
+mod unknown_keys;
+mod validation;
+
+pub(super) use dotenv::extend_with_dotenv;
+pub(super) use enum_variants::unit_variants;
+pub(super) use exec::exec_command;
+pub(super) use files::{
+    apply_profile, collect_config_files, detect_format, discover_config_file, find_default_path,
+    read_env_file, read_secret_file, search_upwards,
+};
+pub(super) use interpolation::interpolate;
+pub(super) use lenient::{lenient_bool, lenient_int};
+pub(super) use secret::redact_error;
+pub(super) use unknown_keys::{check_unknown_keys, check_unused_env};
+pub(super) use validation::nest_error;
 
 pub(super) fn parse_subcommand<T>(
     args: impl Iterator<Item = String>,
@@ -42,11 +66,56 @@ where
         })
 }
 
+/// Whether the raw value (of the command line or the environment)
+/// or the value of the configuration file is null or empty.
+pub(super) fn is_null_or_empty(value: &str) -> bool {
+    matches!(value.trim(), "" | "null" | "\"\"")
+}
+
 pub(super) fn find_field_in_table(
     config: &HashMap<String, config::Value>,
     table: Option<String>,
     field_name: String,
 ) -> Result<Option<String>, crate::Error> {
+    find_value_in_table(config, table, field_name)?
+        .cloned()
+        .map(from_config_to_string)
+        .transpose()
+}
+
+/// Same as [find_field_in_table], but relative path in the string value
+/// is resolved against the directory of the file the value was taken from.
+pub(super) fn find_path_in_table(
+    config: &ConfigFileData,
+    table: Option<String>,
+    field_name: String,
+) -> Result<Option<String>, crate::Error> {
+    let top_level_key = deconstruct_table_path(table.as_deref().unwrap_or(&field_name)).next();
+    let value = match find_value_in_table(&config.values, table, field_name)? {
+        None => return Ok(None),
+        Some(value) => value,
+    };
+
+    let origin_dir = top_level_key
+        .and_then(|key| config.origins.get(&key))
+        .and_then(|origin| origin.parent());
+
+    match (&value.kind, origin_dir) {
+        (config::ValueKind::String(path), Some(dir))
+            if std::path::Path::new(path).is_relative() =>
+        {
+            let path = dir.join(path).to_string_lossy().into_owned();
+            Ok(Some(serde_json::Value::String(path).to_string()))
+        }
+        _ => from_config_to_string(value.clone()).map(Some),
+    }
+}
+
+fn find_value_in_table(
+    config: &HashMap<String, config::Value>,
+    table: Option<String>,
+    field_name: String,
+) -> Result<Option<&config::Value>, crate::Error> {
     let mut field_segs = deconstruct_table_path(&field_name).collect::<Vec<_>>();
     let field = field_segs.pop().ok_or_else(|| {
         crate::Error::FailedParse(format!("Empty path segments of the field: {field_name}"))
@@ -57,20 +126,15 @@ pub(super) fn find_field_in_table(
             None => return Ok(None),
             Some(sub_config) => sub_config,
         },
-        Some(table) => match find_sub_table(
-            config,
-            deconstruct_table_path(&table).chain(field_segs.into_iter()),
-        )? {
-            None => return Ok(None),
-            Some(sub_config) => sub_config,
-        },
+        Some(table) => {
+            match find_sub_table(config, deconstruct_table_path(&table).chain(field_segs))? {
+                None => return Ok(None),
+                Some(sub_config) => sub_config,
+            }
+        }
     };
 
-    if let Some(value) = sub_config.get(&field) {
-        from_config_to_string(value.clone()).map(Some)
-    } else {
-        Ok(None)
-    }
+    Ok(sub_config.get(&field))
 }
 
 fn find_sub_table(
@@ -180,6 +244,14 @@ impl PartialEq for ConfigOption {
                     ConfigOption::ExplicitSource(Source::Env(_)),
                     ConfigOption::ExplicitSource(Source::Env(_)),
                 )
+                | (
+                    ConfigOption::DenyUnknownKeys(_),
+                    ConfigOption::DenyUnknownKeys(_)
+                )
+        ) || matches!(
+            (self, other),
+            (ConfigOption::CustomSource(lhs), ConfigOption::CustomSource(rhs))
+                if Any::type_id(&**lhs) == Any::type_id(&**rhs)
         )
     }
 }
@@ -192,6 +264,11 @@ impl Hash for ConfigOption {
             ConfigOption::ExplicitSource(Source::Clap(_)) => state.write_u8(2),
             ConfigOption::ExplicitSource(Source::ConfigFiles(_)) => state.write_u8(3),
             ConfigOption::ExplicitSource(Source::Env(_)) => state.write_u8(4),
+            ConfigOption::CustomSource(source) => {
+                state.write_u8(5);
+                Any::type_id(&**source).hash(state);
+            }
+            ConfigOption::DenyUnknownKeys(_) => state.write_u8(6),
         }
     }
 }
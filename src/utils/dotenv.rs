@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use std::{collections::HashMap, io::ErrorKind};
+
+use crate::Error;
+
+/// Adds variables of the dotenv file to `env`.
+/// Variables that are already present in `env` are not overwritten.
+pub(crate) fn extend_with_dotenv(
+    env: &mut HashMap<String, String>,
+    path: &str,
+    optional: bool,
+) -> Result<(), Error> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if optional && err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(Error::ExternalError(format!(
+                "failed to read dotenv file {path}: {err}"
+            )))
+        }
+    };
+
+    let vars = parse_dotenv(&content).map_err(|(line, msg)| {
+        Error::FailedParse(format!(
+            "failed to parse dotenv file {path}: line {line}: {msg}"
+        ))
+    })?;
+    for (key, value) in vars {
+        env.entry(key.to_lowercase()).or_insert(value);
+    }
+    Ok(())
+}
+
+/// Parses `KEY=value` lines. Supports comments, `export` prefix,
+/// single-quoted (literal) and double-quoted (with escapes) multiline values.
+fn parse_dotenv(content: &str) -> Result<Vec<(String, String)>, (usize, String)> {
+    let mut res = vec![];
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+
+    loop {
+        // Skip blank lines, leading whitespace and comments
+        while let Some(&c) = chars.peek() {
+            match c {
+                '\n' => line += 1,
+                '#' => {
+                    while chars.peek().is_some_and(|&c| c != '\n') {
+                        chars.next();
+                    }
+                    continue;
+                }
+                c if c.is_whitespace() => {}
+                _ => break,
+            }
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            return Ok(res);
+        }
+
+        let mut declaration = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '=' || c == '\n' {
+                break;
+            }
+            declaration.push(c);
+            chars.next();
+        }
+        if chars.next() != Some('=') {
+            return Err((line, format!("expected `=` after `{}`", declaration.trim())));
+        }
+        let declaration = declaration.trim();
+        let key = declaration
+            .strip_prefix("export")
+            .filter(|key| key.starts_with(char::is_whitespace))
+            .map_or(declaration, str::trim_start);
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            return Err((line, format!("invalid variable name `{key}`")));
+        }
+
+        while chars.peek().is_some_and(|&c| c == ' ' || c == '\t') {
+            chars.next();
+        }
+        let start_line = line;
+        let value = match chars.peek() {
+            Some(&quote @ ('"' | '\'')) => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err((start_line, format!("unterminated {quote} quote"))),
+                        Some(c) if c == quote => break,
+                        Some('\\') if quote == '"' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('r') => value.push('\r'),
+                            Some('t') => value.push('\t'),
+                            Some(c @ ('"' | '\\' | '$')) => value.push(c),
+                            Some(c) => {
+                                value.push('\\');
+                                value.push(c);
+                            }
+                            None => {
+                                return Err((start_line, format!("unterminated {quote} quote")))
+                            }
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            value.push(c);
+                        }
+                    }
+                }
+                let mut rest = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    rest.push(c);
+                    chars.next();
+                }
+                let rest = rest.trim_start();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err((line, format!("unexpected `{rest}` after the quoted value")));
+                }
+                value
+            }
+            _ => {
+                let mut value = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    // Inline comment must be separated by whitespace
+                    if c == '#' && (value.is_empty() || value.ends_with(char::is_whitespace)) {
+                        while chars.peek().is_some_and(|&c| c != '\n') {
+                            chars.next();
+                        }
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                value.trim_end().to_string()
+            }
+        };
+
+        res.push((key.to_string(), value));
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use std::cell::Cell;
+
+use serde::de::{
+    self, value::Error as ProbeError, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor,
+};
+
+/// Serialized names of the variants if `T` is an enumeration with unit variants only
+/// (like `#[derive(Deserialize)] enum LogFormat { Json, Text }`), `rename` and `rename_all` are honoured.
+///
+/// Names are taken from the `deserialize_enum` call of the derived implementation,
+/// then every variant is deserialized as the unit one.
+pub(crate) fn unit_variants<T: DeserializeOwned>() -> Option<Vec<&'static str>> {
+    let variants = Cell::new(None);
+    let _ = T::deserialize(VariantsProbe {
+        chosen: None,
+        variants: &variants,
+    });
+    let variants = variants.get()?;
+
+    let all_unit = (0..variants.len()).all(|chosen| {
+        T::deserialize(VariantsProbe {
+            chosen: Some(chosen),
+            variants: &Cell::new(None),
+        })
+        .is_ok()
+    });
+    (all_unit && !variants.is_empty()).then(|| variants.to_vec())
+}
+
+struct VariantsProbe<'a> {
+    /// Variant to deserialize, `None` to collect the names only.
+    chosen: Option<usize>,
+    variants: &'a Cell<Option<&'static [&'static str]>>,
+}
+
+impl<'de> de::Deserializer<'de> for VariantsProbe<'_> {
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not an enumeration"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.variants.set(Some(variants));
+        match self.chosen.and_then(|chosen| variants.get(chosen)) {
+            Some(variant) => visitor.visit_enum(UnitVariant(variant)),
+            None => Err(de::Error::custom("variants are collected")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct UnitVariant(&'static str);
+
+impl<'de> de::EnumAccess<'de> for UnitVariant {
+    type Error = ProbeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.0.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for UnitVariant {
+    type Error = ProbeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        _seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        Err(de::Error::custom("not a unit variant"))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a unit variant"))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a unit variant"))
+    }
+}
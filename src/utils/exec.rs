@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use std::{
+    io::Read,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::Error;
+
+/// Runs the command (without shell) and returns its stdout with trimmed whitespaces.
+pub(crate) fn exec_command(command: &[&str], timeout: Duration) -> Result<String, Error> {
+    let err = |message: String| Error::ExecFailed {
+        command: command.iter().map(ToString::to_string).collect(),
+        message,
+    };
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| err("command is empty".into()))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| err(format!("failed to start: {e}")))?;
+
+    // Pipes are drained in separate threads, so the child doesn't block on a full pipe
+    let read_pipe = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = read_pipe(child.stdout.take().map(|p| Box::new(p) as _));
+    let stderr = read_pipe(child.stderr.take().map(|p| Box::new(p) as _));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err(format!("timed out after {timeout:?}")));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(e) => return Err(err(format!("failed to wait: {e}"))),
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        return Err(err(format!(
+            "{status}: {}",
+            String::from_utf8_lossy(&stderr).trim()
+        )));
+    }
+
+    String::from_utf8(stdout)
+        .map(|stdout| stdout.trim().to_string())
+        .map_err(|e| err(format!("stdout is not valid UTF-8: {e}")))
+}
@@ -0,0 +1,429 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use config::{FileFormat, Source};
+
+use crate::{
+    __private::{ConfigFileData, EnvData},
+    Error, FileOptions,
+};
+
+const FORMATS: &[(FileFormat, &[&str])] = &[
+    (FileFormat::Toml, &["toml"]),
+    (FileFormat::Yaml, &["yaml", "yml"]),
+    (FileFormat::Json, &["json"]),
+    (FileFormat::Json5, &["json5"]),
+    (FileFormat::Ron, &["ron"]),
+];
+
+pub(crate) fn collect_config_files(files: Vec<FileOptions>) -> Result<ConfigFileData, Error> {
+    let mut res = ConfigFileData {
+        values: HashMap::new(),
+        files: vec![],
+        origins: HashMap::new(),
+        profile: None,
+    };
+    for file in files {
+        match file {
+            FileOptions::File { format, path } => res.add_file(path.into(), format)?,
+            FileOptions::Directory { path, glob } => {
+                for (format, file_path) in directory_files(&path, glob.as_deref())? {
+                    res.add_file(file_path, format)?;
+                }
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Reserved key of the configuration file with the list of files to include.
+const INCLUDE_KEY: &str = "include";
+
+impl ConfigFileData {
+    fn add_file(&mut self, path: PathBuf, format: FileFormat) -> Result<(), Error> {
+        self.add_file_with_includes(path, format, &mut vec![])
+    }
+
+    /// Included files are added before the including one, so the including file wins.
+    /// `stack` contains the files that are being included to detect cycles.
+    fn add_file_with_includes(
+        &mut self,
+        path: PathBuf,
+        format: FileFormat,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<(), Error> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if stack.contains(&canonical) {
+            stack.push(canonical);
+            let cycle = stack
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            return Err(Error::FailedParse(format!(
+                "cyclic include of configuration files: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        let mut values = collect_file(&path.to_string_lossy(), format)?;
+        if let Some(include) = values.remove(INCLUDE_KEY) {
+            stack.push(canonical);
+            for included in included_paths(&path, include)? {
+                // Format of the including file is used if it can't be detected by the extension
+                let included_format = format_from_extension(&included).unwrap_or(format);
+                self.add_file_with_includes(included, included_format, stack)?;
+            }
+            stack.pop();
+        }
+
+        for key in values.keys() {
+            self.origins.insert(key.clone(), path.clone());
+        }
+        deep_merge(&mut self.values, values);
+        self.files.push(path);
+        Ok(())
+    }
+}
+
+/// Reserved key of the configuration file with the table of profiles.
+const PROFILE_KEY: &str = "profile";
+
+/// Deep-merges the `[profile.<name>]` table over the root one.
+/// The profile table is removed from the data even if no profile is selected.
+/// Unknown `selected` profile (by `clap` or `env`) is an error, missing `default` one is skipped.
+pub(crate) fn apply_profile(
+    data: &mut ConfigFileData,
+    selected: Option<&str>,
+    default: Option<&str>,
+) -> Result<(), Error> {
+    let profiles = data.values.remove(PROFILE_KEY);
+    let origin = data.origins.remove(PROFILE_KEY);
+    let (profile, required) = match (selected, default) {
+        (Some(profile), _) => (profile, true),
+        (None, Some(profile)) => (profile, false),
+        (None, None) => return Ok(()),
+    };
+
+    let mut profiles = match profiles.map(|profiles| profiles.kind) {
+        Some(config::ValueKind::Table(profiles)) => profiles,
+        None => HashMap::new(),
+        Some(_) => {
+            return Err(Error::FailedParse(format!(
+                "{PROFILE_KEY} of the configuration files must be a table of profiles"
+            )))
+        }
+    };
+    let overlay = match profiles.remove(profile).map(|overlay| overlay.kind) {
+        Some(config::ValueKind::Table(overlay)) => overlay,
+        Some(_) => {
+            return Err(Error::FailedParse(format!(
+                "{PROFILE_KEY}.{profile} of the configuration files must be a table"
+            )))
+        }
+        None if !required => return Ok(()),
+        None => {
+            let mut available = profiles.into_keys().collect::<Vec<_>>();
+            available.sort();
+            return Err(Error::FailedParse(format!(
+                "unknown profile {profile}, available profiles: [{}]",
+                available.join(", ")
+            )));
+        }
+    };
+
+    if let Some(origin) = origin {
+        for key in overlay.keys() {
+            data.origins.insert(key.clone(), origin.clone());
+        }
+    }
+    deep_merge(&mut data.values, overlay);
+    data.profile = Some(profile.to_string());
+    Ok(())
+}
+
+/// Tables are merged recursively, other values of the `overlay` replace the `base` ones.
+fn deep_merge(base: &mut HashMap<String, config::Value>, overlay: HashMap<String, config::Value>) {
+    for (key, value) in overlay {
+        if let (
+            Some(config::Value {
+                kind: config::ValueKind::Table(base_table),
+                ..
+            }),
+            config::ValueKind::Table(_),
+        ) = (base.get_mut(&key), &value.kind)
+        {
+            if let config::ValueKind::Table(table) = value.kind {
+                deep_merge(base_table, table);
+            }
+            continue;
+        }
+        base.insert(key, value);
+    }
+}
+
+/// Paths of the `include` key resolved against the directory of the including file.
+fn included_paths(path: &Path, include: config::Value) -> Result<Vec<PathBuf>, Error> {
+    let invalid = || {
+        Error::FailedParse(format!(
+            "{INCLUDE_KEY} of the configuration file {} must be a string or a list of strings",
+            path.display()
+        ))
+    };
+    let included = match include.kind {
+        config::ValueKind::String(included) => vec![included],
+        config::ValueKind::Array(array) => array
+            .into_iter()
+            .map(|value| match value.kind {
+                config::ValueKind::String(included) => Ok(included),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err(invalid()),
+    };
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    Ok(included
+        .into_iter()
+        .map(|included| dir.join(included))
+        .collect())
+}
+
+fn collect_file(path: &str, format: FileFormat) -> Result<HashMap<String, config::Value>, Error> {
+    config::File::new(path, format)
+        .collect()
+        .map_err(|err| Error::ExternalError(format!("failed to collect config file {path}: {err}")))
+}
+
+fn directory_files(dir: &str, glob: Option<&str>) -> Result<Vec<(FileFormat, PathBuf)>, Error> {
+    let pattern = glob
+        .map(|glob| {
+            glob::Pattern::new(glob)
+                .map_err(|err| Error::ExternalError(format!("invalid glob pattern {glob}: {err}")))
+        })
+        .transpose()?;
+    let read_err =
+        |err| Error::ExternalError(format!("failed to read config directory {dir}: {err}"));
+
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir).map_err(read_err)? {
+        let path = entry.map_err(read_err)?.path();
+        let name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) if !name.starts_with('.') && path.is_file() => name,
+            _ => continue,
+        };
+        if let Some(pattern) = &pattern {
+            if !pattern.matches(name) {
+                continue;
+            }
+        }
+
+        match format_from_extension(&path) {
+            Some(format) => files.push((format, path)),
+            None if pattern.is_some() => return Err(unknown_format(&path)),
+            None => continue,
+        }
+    }
+
+    files.sort_by(|(_, lhs), (_, rhs)| lhs.file_name().cmp(&rhs.file_name()));
+    Ok(files)
+}
+
+/// Returns the first existing candidate.
+/// If none of them exists, the error lists the checked and the skipped candidates
+/// (unless the only candidate is loaded to report its error).
+pub(crate) fn find_default_path(
+    candidates: &[&str],
+    env_data: &EnvData,
+    optional: bool,
+) -> Result<Option<String>, Error> {
+    let mut missing = vec![];
+    for candidate in candidates {
+        match expand_path(candidate, env_data) {
+            Ok(path) if Path::new(&path).exists() => return Ok(Some(path)),
+            Ok(path) if candidates.len() == 1 && !optional => return Ok(Some(path)),
+            Ok(path) => missing.push(format!("`{path}` does not exist")),
+            Err(reason) => missing.push(format!("`{candidate}` is skipped: {reason}")),
+        }
+    }
+
+    if optional {
+        Ok(None)
+    } else {
+        Err(Error::ExternalError(format!(
+            "no default configuration file found: {}",
+            missing.join(", ")
+        )))
+    }
+}
+
+/// Expands leading `~` and `$VAR`/`${VAR}` in the path.
+/// Returns the reason if the path can't be expanded (like a variable that is not set).
+fn expand_path(path: &str, env_data: &EnvData) -> Result<String, String> {
+    let get = |name: &str| {
+        env_data
+            .get(name)
+            .ok_or_else(|| format!("variable `{name}` is not set"))
+    };
+
+    let mut res = String::new();
+    let mut rest = path;
+    if let Some(stripped) = rest.strip_prefix('~') {
+        if stripped.is_empty() || stripped.starts_with(std::path::is_separator) {
+            res.push_str(get("HOME")?);
+            rest = stripped;
+        }
+    }
+
+    while let Some(pos) = rest.find('$') {
+        res.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        let (name, tail) = match rest.strip_prefix('{') {
+            Some(braced) => {
+                let end = braced.find('}').ok_or("`${` is not closed")?;
+                (&braced[..end], &braced[end + 1..])
+            }
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+        res.push_str(get(name)?);
+        rest = tail;
+    }
+    res.push_str(rest);
+    Ok(res)
+}
+
+pub(crate) fn discover_config_file(
+    name: &str,
+    format: Option<FileFormat>,
+    env_data: &EnvData,
+) -> Option<FileOptions> {
+    let exe_suffix = std::env::consts::EXE_SUFFIX;
+    let name = match name.strip_suffix(exe_suffix) {
+        Some(stripped) if !exe_suffix.is_empty() => stripped,
+        _ => name,
+    };
+
+    let mut config_dirs = vec![];
+    match env_data.get("xdg_config_home") {
+        Some(config_home) if !config_home.is_empty() => {
+            config_dirs.push(PathBuf::from(config_home))
+        }
+        _ => config_dirs.extend(
+            env_data
+                .get("home")
+                .map(|home| Path::new(home).join(".config")),
+        ),
+    }
+    let system_dirs = env_data
+        .get("xdg_config_dirs")
+        .filter(|dirs| !dirs.is_empty())
+        .map_or("/etc/xdg", String::as_str);
+    config_dirs.extend(std::env::split_paths(system_dirs));
+
+    // `./<name>.<ext>` first, then `<dir>/<name>/<name>.<ext>` and `<dir>/<name>.<ext>` of every config directory
+    let candidates = std::iter::once(PathBuf::from(name)).chain(
+        config_dirs
+            .into_iter()
+            .flat_map(|dir| [dir.join(name).join(name), dir.join(name)]),
+    );
+
+    for candidate in candidates {
+        for (candidate_format, extensions) in FORMATS {
+            if format.is_some_and(|format| format != *candidate_format) {
+                continue;
+            }
+            for extension in *extensions {
+                let mut path = candidate.clone().into_os_string();
+                path.push(".");
+                path.push(extension);
+                let path = PathBuf::from(path);
+                if path.is_file() {
+                    return Some(FileOptions::File {
+                        format: *candidate_format,
+                        path: path.to_string_lossy().into_owned(),
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Looks for the `name` file in the current directory and all its ancestors.
+/// Returns the nearest one or, if `all` is set, all of them starting from the farthest.
+pub(crate) fn search_upwards(name: &str, all: bool) -> Result<Vec<String>, Error> {
+    let cwd = std::env::current_dir().map_err(|err| {
+        Error::ExternalError(format!("failed to get the current directory: {err}"))
+    })?;
+
+    let mut found = vec![];
+    for dir in cwd.ancestors() {
+        let path = dir.join(name);
+        if path.is_file() {
+            found.push(path.to_string_lossy().into_owned());
+            if !all {
+                break;
+            }
+        }
+    }
+    found.reverse();
+    Ok(found)
+}
+
+/// Reads the file, path to which is set by the `key` environment variable.
+pub(crate) fn read_env_file(env_data: &EnvData, key: &str) -> Result<Option<String>, Error> {
+    match env_data.get(key) {
+        None => Ok(None),
+        Some(path) => read_secret(Path::new(path)).map(Some).map_err(|err| {
+            Error::ExternalError(format!("failed to read file {path} set by {key}: {err}"))
+        }),
+    }
+}
+
+/// Reads the `name` file of the secrets directory. Missing file is not an error.
+pub(crate) fn read_secret_file(dir: &str, name: &str) -> Result<Option<String>, Error> {
+    let path = Path::new(dir).join(name);
+    match read_secret(&path) {
+        Ok(secret) => Ok(Some(secret)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Error::ExternalError(format!(
+            "failed to read secret file {}: {err}",
+            path.display()
+        ))),
+    }
+}
+
+fn read_secret(path: &Path) -> std::io::Result<String> {
+    let mut secret = std::fs::read_to_string(path)?;
+    let trimmed_len = secret.trim_end_matches(['\n', '\r']).len();
+    secret.truncate(trimmed_len);
+    Ok(secret)
+}
+
+pub(crate) fn detect_format(path: &str) -> Result<FileFormat, Error> {
+    format_from_extension(Path::new(path)).ok_or_else(|| unknown_format(Path::new(path)))
+}
+
+fn format_from_extension(path: &Path) -> Option<FileFormat> {
+    let extension = path.extension()?.to_str()?;
+    FORMATS
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension))
+        .map(|(format, _)| *format)
+}
+
+fn unknown_format(path: &Path) -> Error {
+    Error::ExternalError(format!(
+        "failed to detect format of config file {} by its extension",
+        path.display()
+    ))
+}
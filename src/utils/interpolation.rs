@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use std::collections::HashMap;
+
+use crate::{
+    __private::{ConfigFileData, EnvData},
+    Error,
+};
+
+/// Expands `${NAME}`, `${NAME:-default}` and `${NAME-default}` in the value.
+/// `$$` is the escaped `$`.
+///
+/// Variables are looked up in the environment and, if `config` is set, in the configuration files
+/// (by the dotted path of the key). `field` is used in the error messages only.
+pub(crate) fn interpolate(
+    value: String,
+    json: bool,
+    env_data: &EnvData,
+    config: Option<&ConfigFileData>,
+    field: &str,
+) -> Result<String, Error> {
+    if !value.contains('$') {
+        return Ok(value);
+    }
+
+    let interpolator = Interpolator {
+        env_data,
+        config: config.map(|config| &config.values),
+        field,
+    };
+    if !json {
+        return interpolator.expand(&value, &mut vec![]);
+    }
+
+    // Only the strings of the structured value are expanded,
+    // so the expanded values don't break its syntax
+    let mut parsed = serde_json::from_str(&value)
+        .map_err(|err| Error::FailedParse(format!("failed to interpolate field {field}: {err}")))?;
+    interpolator.expand_json(&mut parsed)?;
+    Ok(parsed.to_string())
+}
+
+struct Interpolator<'a> {
+    env_data: &'a EnvData,
+    config: Option<&'a HashMap<String, config::Value>>,
+    field: &'a str,
+}
+
+impl Interpolator<'_> {
+    fn expand_json(&self, value: &mut serde_json::Value) -> Result<(), Error> {
+        match value {
+            serde_json::Value::String(s) => *s = self.expand(s, &mut vec![])?,
+            serde_json::Value::Array(array) => {
+                for value in array {
+                    self.expand_json(value)?;
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for value in map.values_mut() {
+                    self.expand_json(value)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// `stack` contains the configuration keys that are being expanded to detect cycles.
+    fn expand(&self, value: &str, stack: &mut Vec<String>) -> Result<String, Error> {
+        let mut res = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(pos) = rest.find('$') {
+            res.push_str(&rest[..pos]);
+            rest = &rest[pos + 1..];
+
+            if let Some(tail) = rest.strip_prefix('$') {
+                res.push('$');
+                rest = tail;
+                continue;
+            }
+            let Some(tail) = rest.strip_prefix('{') else {
+                res.push('$');
+                continue;
+            };
+
+            let end = closing_brace(tail).ok_or_else(|| {
+                Error::FailedParse(format!(
+                    "unclosed `${{` in the value of field {}: {value}",
+                    self.field
+                ))
+            })?;
+            res.push_str(&self.expand_variable(&tail[..end], stack)?);
+            rest = &tail[end + 1..];
+        }
+        res.push_str(rest);
+        Ok(res)
+    }
+
+    fn expand_variable(&self, expr: &str, stack: &mut Vec<String>) -> Result<String, Error> {
+        let (name, default) = match expr.find(['-', ':']) {
+            Some(pos) if expr[pos..].starts_with(":-") => {
+                (&expr[..pos], Some((&expr[pos + 2..], true)))
+            }
+            Some(pos) if expr[pos..].starts_with('-') => {
+                (&expr[..pos], Some((&expr[pos + 1..], false)))
+            }
+            _ => (expr, None),
+        };
+        if name.is_empty() {
+            return Err(Error::FailedParse(format!(
+                "empty variable name in `${{{expr}}}` of field {}",
+                self.field
+            )));
+        }
+
+        if stack.iter().any(|var| var == name) {
+            stack.push(name.to_string());
+            return Err(Error::FailedParse(format!(
+                "cyclic variable reference in field {}: {}",
+                self.field,
+                stack.join(" -> ")
+            )));
+        }
+
+        let found = self.lookup(name)?;
+        let found = match (found, default) {
+            // `:-` takes the default for the empty values too
+            (Some(Found::Env(found) | Found::Config(found)), Some((_, true)))
+                if found.is_empty() =>
+            {
+                None
+            }
+            (found, _) => found,
+        };
+
+        match (found, default) {
+            // Values of the environment variables are taken as is
+            (Some(Found::Env(found)), _) => Ok(found),
+            (Some(Found::Config(found)), _) => {
+                stack.push(name.to_string());
+                let expanded = self.expand(&found, stack)?;
+                stack.pop();
+                Ok(expanded)
+            }
+            (None, Some((default, _))) => self.expand(default, stack),
+            (None, None) => Err(Error::MissingArgument(format!(
+                "variable {name} used in field {} is not defined",
+                self.field
+            ))),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Result<Option<Found>, Error> {
+        if let Some(value) = self.env_data.get(name) {
+            return Ok(Some(Found::Env(value.clone())));
+        }
+        let Some(config) = self.config else {
+            return Ok(None);
+        };
+
+        Ok(
+            match super::find_value_in_table(config, None, name.to_string())? {
+                None => None,
+                Some(config::Value {
+                    kind: config::ValueKind::String(s),
+                    ..
+                }) => Some(Found::Config(s.clone())),
+                Some(value) => Some(Found::Config(super::from_config_to_string(value.clone())?)),
+            },
+        )
+    }
+}
+
+/// Value of the variable by its origin:
+/// the keys of the configuration files may reference other variables, environment values are final.
+enum Found {
+    Env(String),
+    Config(String),
+}
+
+/// Position of the brace closing the `${`, nested `${...}` are skipped.
+fn closing_brace(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '$' if chars.peek().is_some_and(|(_, c)| *c == '{') => {
+                chars.next();
+                depth += 1;
+            }
+            '}' if depth == 0 => return Some(pos),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+/// Normalizes the boolean written in the shell conventions
+/// (`yes`/`no`, `y`/`n`, `on`/`off`, `1`/`0`, `true`/`false` in any case, surrounded by whitespaces).
+/// Unknown values are returned as is to be reported by the deserializer.
+pub(crate) fn lenient_bool(value: String) -> String {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "on" | "1" => "true".to_string(),
+        "false" | "no" | "n" | "off" | "0" => "false".to_string(),
+        _ => value,
+    }
+}
+
+/// Normalizes the integer with the underscores (`1_000`), `0x`/`0o`/`0b` prefixes
+/// and the surrounding whitespaces to the decimal form.
+/// Unknown values are returned as is to be reported by the deserializer.
+pub(crate) fn lenient_int(value: String) -> String {
+    let trimmed = value.trim();
+    let (sign, unsigned) = match trimmed.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let digits = unsigned.replace('_', "");
+    let (radix, digits) = match digits.get(..2).map(str::to_ascii_lowercase).as_deref() {
+        Some("0x") => (16, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        _ => (10, digits.as_str()),
+    };
+    if digits.starts_with('+') {
+        return value;
+    }
+    match u128::from_str_radix(digits, radix) {
+        Ok(number) => format!("{sign}{number}"),
+        Err(_) => value,
+    }
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use crate::Error;
+
+const MASK: &str = "***";
+
+/// Replaces the secret value in the message with the mask.
+/// The unquoted form of the JSON string is masked too.
+fn redact(message: &str, secret: &str) -> String {
+    let unquoted = serde_json::from_str::<String>(secret).ok();
+    [Some(secret), unquoted.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|secret| !secret.is_empty())
+        .fold(message.to_string(), |message, secret| {
+            message.replace(secret, MASK)
+        })
+}
+
+/// Masks the secret value in the messages of the error.
+pub(crate) fn redact_error(err: Error, secret: &str) -> Error {
+    match err {
+        Error::MissingArgument(msg) => Error::MissingArgument(redact(&msg, secret)),
+        Error::FailedParse(msg) => Error::FailedParse(redact(&msg, secret)),
+        Error::ExternalError(msg) => Error::ExternalError(redact(&msg, secret)),
+        Error::ExecFailed { command, message } => Error::ExecFailed {
+            command,
+            message: redact(&message, secret),
+        },
+        Error::ValidationFailed {
+            field,
+            source,
+            message,
+        } => Error::ValidationFailed {
+            field,
+            source,
+            message: redact(&message, secret),
+        },
+        Error::StructValidationFailed { path, message } => Error::StructValidationFailed {
+            path,
+            message: redact(&message, secret),
+        },
+    }
+}
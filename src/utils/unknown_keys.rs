@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use std::collections::HashMap;
+
+use crate::{
+    __private::{ConfigFileData, EnvData},
+    Error,
+};
+
+/// Checks that all the keys of the configuration files are consumed by the fields.
+/// `consumed` contains full dotted paths of the keys (with the table).
+/// A consumed table consumes all its nested keys.
+pub(crate) fn check_unknown_keys(data: &ConfigFileData, consumed: &[String]) -> Result<(), Error> {
+    let mut leaves = vec![];
+    collect_leaves(&data.values, "", &mut leaves);
+    leaves.sort();
+
+    let unknown = leaves
+        .into_iter()
+        .filter(|leaf| !consumed.iter().any(|key| covers(key, leaf)))
+        .map(|leaf| {
+            let mut msg = format!("`{leaf}`");
+            let top_level_key = leaf.split('.').next().unwrap_or_default();
+            if let Some(origin) = data.origins.get(top_level_key) {
+                msg.push_str(&format!(" in {}", origin.display()));
+            }
+            if let Some(suggestion) = did_you_mean(&leaf, consumed.iter().map(String::as_str)) {
+                msg.push_str(&format!(" (did you mean `{suggestion}`?)"));
+            }
+            msg
+        })
+        .collect::<Vec<_>>();
+
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::FailedParse(format!(
+            "unknown keys in configuration files: {}",
+            unknown.join(", ")
+        )))
+    }
+}
+
+/// Finds the environment variables with the `prefix` that are not consumed by any field.
+/// Their names are returned or, if `deny` is set, reported as an error.
+pub(crate) fn check_unused_env(
+    env_data: &EnvData,
+    prefix: &str,
+    consumed: &[String],
+    deny: bool,
+) -> Result<Vec<String>, Error> {
+    if prefix.is_empty() {
+        return Ok(vec![]);
+    }
+    let prefix = format!("{}_", prefix.to_lowercase());
+    let consumed = consumed
+        .iter()
+        .map(|key| key.to_lowercase())
+        .collect::<Vec<_>>();
+
+    let mut unused = env_data
+        .keys()
+        .filter(|key| key.starts_with(&prefix) && !consumed.contains(key))
+        .collect::<Vec<_>>();
+    unused.sort();
+
+    if unused.is_empty() || !deny {
+        return Ok(unused.into_iter().map(|key| key.to_uppercase()).collect());
+    }
+
+    let unused = unused
+        .into_iter()
+        .map(|key| {
+            let mut msg = format!("`{}`", key.to_uppercase());
+            if let Some(suggestion) = did_you_mean(key, consumed.iter().map(String::as_str)) {
+                msg.push_str(&format!(" (did you mean `{}`?)", suggestion.to_uppercase()));
+            }
+            msg
+        })
+        .collect::<Vec<_>>();
+    Err(Error::FailedParse(format!(
+        "unused environment variables: {}",
+        unused.join(", ")
+    )))
+}
+
+/// Paths of the non-table values and of the empty tables.
+fn collect_leaves(table: &HashMap<String, config::Value>, prefix: &str, leaves: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match &value.kind {
+            config::ValueKind::Table(nested) if !nested.is_empty() => {
+                collect_leaves(nested, &path, leaves)
+            }
+            _ => leaves.push(path),
+        }
+    }
+}
+
+fn covers(key: &str, leaf: &str) -> bool {
+    let is_nested = |path: &str, parent: &str| {
+        path.strip_prefix(parent)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    };
+    // Empty table is known if any of its nested keys is consumed
+    is_nested(leaf, key) || is_nested(key, leaf)
+}
+
+/// The most similar candidate, if it is similar enough.
+pub(crate) fn did_you_mean<'a>(
+    unknown: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (edit_distance(unknown, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.chars().count() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance, transposition of the adjacent characters counts as a single edit.
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let lhs = lhs.chars().collect::<Vec<_>>();
+    let rhs = rhs.chars().collect::<Vec<_>>();
+
+    // d[i][j] is the distance between lhs[..i] and rhs[..j]
+    let mut d = vec![vec![0; rhs.len() + 1]; lhs.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=lhs.len() {
+        for j in 1..=rhs.len() {
+            let cost = usize::from(lhs[i - 1] != rhs[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && lhs[i - 1] == rhs[j - 2] && lhs[i - 2] == rhs[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[lhs.len()][rhs.len()]
+}
@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2022 JSRPC “Kryptonite”
+
+use crate::Error;
+
+/// Prefixes the path of the validation error with the name of the flatten field it came from.
+pub(crate) fn nest_error(err: Error, field: &str) -> Error {
+    let nest = |path: String| {
+        if path.is_empty() {
+            field.to_string()
+        } else {
+            format!("{field}.{path}")
+        }
+    };
+    match err {
+        Error::ValidationFailed {
+            field,
+            source,
+            message,
+        } => Error::ValidationFailed {
+            field: nest(field),
+            source,
+            message,
+        },
+        Error::StructValidationFailed { path, message } => Error::StructValidationFailed {
+            path: nest(path),
+            message,
+        },
+        err => err,
+    }
+}
@@ -1,18 +1,35 @@
 mod parse_method {
     mod clap;
+    mod constraints;
+    mod count;
+    mod custom;
     mod default;
     mod deserialize_with;
+    mod dotenv;
     mod empty;
     mod env;
+    mod exec;
     mod file;
     mod flatten;
     mod get_command;
+    mod include;
     mod init_from;
     mod integration_test;
+    mod interpolation;
     mod layers;
+    mod lenient;
+    mod negatable;
+    mod optional;
     mod parse_options;
+    mod profiles;
+    mod secret;
+    mod struct_validation;
     mod subcommand;
     mod tables;
+    mod unknown_keys;
+    mod unused_env;
+    mod validate;
+    mod value_parser;
 }
 
 fn test_env(tests: Vec<fn()>) {
@@ -0,0 +1,61 @@
+use config_manager::{config, ConfigInit};
+
+use crate::{set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "cns",
+    file(format = "toml", env = "cns_config", optional),
+    __debug_cmd_input__()
+)]
+struct Cluster {
+    #[source(clap(long), env, config, default = "single")]
+    mode: String,
+    #[source(env, config, default, required_if(field = "mode", eq = "cluster"))]
+    seeds: Vec<String>,
+    #[source(env, config, default, conflicts_with = "password_file")]
+    password: String,
+    #[source(clap(long), env, config, default)]
+    password_file: String,
+}
+
+fn satisfied() {
+    set_env("CNS_MODE", "cluster");
+    set_env("CNS_SEEDS", "[\"node1\", \"node2\"]");
+    set_env("CNS_PASSWORD", "secret");
+    assert_eq!(
+        Cluster::parse().unwrap(),
+        Cluster {
+            mode: "cluster".into(),
+            seeds: vec!["node1".into(), "node2".into()],
+            password: "secret".into(),
+            password_file: "".into(),
+        }
+    );
+}
+
+fn required() {
+    set_env("CNS_MODE", "cluster");
+    assert_eq!(
+        Cluster::parse().unwrap_err().to_string(),
+        "field seeds is required when mode is \"cluster\""
+    );
+
+    set_env("CNS_MODE", "single");
+    assert!(Cluster::parse().is_ok());
+}
+
+fn conflicts() {
+    set_env("CNS_PASSWORD", "secret");
+    set_env("CNS_CONFIG", "./tests/data/constraints.toml");
+    assert_eq!(
+        Cluster::parse().unwrap_err().to_string(),
+        "invalid value of field password taken from env: \
+        conflicts with field password_file taken from configuration file"
+    );
+}
+
+#[test]
+fn constraints() {
+    test_env(vec![satisfied, required, conflicts]);
+}
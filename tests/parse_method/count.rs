@@ -0,0 +1,54 @@
+use config_manager::{config, ConfigInit};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "cnt", __debug_cmd_input__("-vvv", "--quiet", "--quiet"))]
+struct Counted {
+    #[source(clap(short, count), env, default = 1)]
+    verbose: u8,
+    #[source(clap(long, count), env)]
+    quiet: Option<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "cnt", __debug_cmd_input__())]
+struct NotCounted {
+    #[source(clap(short, count), env, default = 1)]
+    verbose: u8,
+    #[source(clap(long, count), env)]
+    quiet: Option<u32>,
+}
+
+fn count() {
+    set_env("CNT_VERBOSE", 5);
+    assert_ok_and_compare(&Counted {
+        verbose: 3,
+        quiet: Some(2),
+    });
+}
+
+fn fallback() {
+    assert_ok_and_compare(&NotCounted {
+        verbose: 1,
+        quiet: None,
+    });
+
+    set_env("CNT_VERBOSE", 5);
+    set_env("CNT_QUIET", 0);
+    assert_ok_and_compare(&NotCounted {
+        verbose: 5,
+        quiet: Some(0),
+    });
+}
+
+fn help() {
+    let help = Counted::get_command().render_help().to_string();
+    assert!(help.contains("-v, --verbose..."), "{help}");
+    assert!(help.contains("--quiet..."), "{help}");
+}
+
+#[test]
+fn count_flags() {
+    test_env(vec![count, fallback, help]);
+}
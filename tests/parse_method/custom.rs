@@ -0,0 +1,127 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use config_manager::{config, ConfigInit, ConfigOption, Error, Flatten, SourceValue, ValueSource};
+use serde::Deserialize;
+
+use crate::{set_env, test_env};
+
+struct KvStore(HashMap<&'static str, SourceValue>);
+
+impl ValueSource for KvStore {
+    fn get(&self, key: &str) -> Result<Option<SourceValue>, Error> {
+        Ok(self.0.get(key).cloned())
+    }
+}
+
+struct Unavailable;
+
+impl ValueSource for Unavailable {
+    fn get(&self, _key: &str) -> Result<Option<SourceValue>, Error> {
+        Err(Error::ExternalError("storage is unavailable".into()))
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+struct Nested {
+    #[source(custom(path = KvStore, key = "nested.level"))]
+    level: u8,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(__debug_cmd_input__())]
+struct Custom {
+    #[source(custom(path = KvStore, key = "db.url"))]
+    url: String,
+    #[source(custom(path = KvStore))]
+    ports: Vec<u16>,
+    #[source(env = "custom_name", custom(path = KvStore), default = "default")]
+    name: String,
+    #[source(custom(path = Unavailable), default = 0)]
+    _unused: i32,
+    #[flatten]
+    nested: Nested,
+}
+
+fn kv_store() -> ConfigOption {
+    ConfigOption::CustomSource(Arc::new(KvStore(HashMap::from([
+        ("db.url", "postgres://localhost".into()),
+        ("ports", serde_json::json!([80, 443]).into()),
+        ("name", "from store".into()),
+        ("nested.level", "3".into()),
+    ]))))
+}
+
+fn registered() {
+    let custom = Custom::parse_options(HashSet::from([
+        kv_store(),
+        ConfigOption::CustomSource(Arc::new(Unavailable)),
+    ]));
+    match custom {
+        Err(Error::ExternalError(msg)) => assert!(msg.contains("unavailable"), "{msg}"),
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[config(__debug_cmd_input__())]
+    struct Ordered {
+        #[source(custom(path = KvStore, key = "db.url"))]
+        url: String,
+        #[source(env = "custom_name", custom(path = KvStore))]
+        name: String,
+        #[source(custom(path = KvStore, key = "missing"), default = "default")]
+        missing: String,
+        #[flatten]
+        nested: Nested,
+    }
+
+    let expected = Ordered {
+        url: "postgres://localhost".into(),
+        name: "from store".into(),
+        missing: "default".into(),
+        nested: Nested { level: 3 },
+    };
+    assert_eq!(
+        Ordered::parse_options(HashSet::from([kv_store()])).unwrap(),
+        expected
+    );
+
+    set_env("custom_name", "from env");
+    assert_eq!(
+        Ordered::parse_options(HashSet::from([kv_store()])).unwrap(),
+        Ordered {
+            name: "from env".into(),
+            ..expected
+        }
+    );
+}
+
+fn structured() {
+    #[derive(Debug, PartialEq)]
+    #[config(__debug_cmd_input__())]
+    struct Structured {
+        #[source(custom(path = KvStore))]
+        ports: Vec<u16>,
+    }
+
+    assert_eq!(
+        Structured::parse_options(HashSet::from([kv_store()])).unwrap(),
+        Structured {
+            ports: vec![80, 443]
+        }
+    );
+}
+
+fn not_registered() {
+    assert!(matches!(
+        Custom::parse(),
+        Err(Error::ExternalError(msg)) if msg.contains("is not registered")
+    ));
+}
+
+#[test]
+fn custom() {
+    test_env(vec![registered, structured, not_registered]);
+}
@@ -0,0 +1,95 @@
+use config_manager::{config, ConfigInit};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+fn raw(s: &str) -> Result<String, String> {
+    Ok(s.to_string())
+}
+
+fn dotenv_syntax() {
+    #[derive(Debug, PartialEq)]
+    #[config(dotenv = "./tests/data/app.env", __debug_cmd_input__())]
+    struct Syntax {
+        #[source(env = "dotenv_host")]
+        host: String,
+        #[source(env = "dotenv_port")]
+        port: u16,
+        #[source(env = "dotenv_name", deserialize_with = "raw")]
+        name: String,
+        #[source(env = "dotenv_raw", deserialize_with = "raw")]
+        raw: String,
+        #[source(env = "dotenv_hash", deserialize_with = "raw")]
+        hash: String,
+        #[source(env = "dotenv_overridden")]
+        overridden: String,
+    }
+
+    set_env("DOTENV_OVERRIDDEN", "from_env");
+    assert_ok_and_compare(&Syntax {
+        host: "localhost".into(),
+        port: 8080,
+        name: "quoted \"name\"".into(),
+        raw: "single $raw # not a comment".into(),
+        hash: "abc#def".into(),
+        overridden: "from_env".into(),
+    });
+    assert!(std::env::var("DOTENV_HOST").is_err());
+}
+
+fn dotenv_path() {
+    #[derive(Debug, PartialEq)]
+    #[config(
+        dotenv(
+            clap(long = "env-file"),
+            env = "dotenv_path",
+            default = "./tests/data/app.env"
+        ),
+        __debug_cmd_input__()
+    )]
+    struct DefaultPath {
+        #[source(env = "dotenv_port")]
+        port: u16,
+    }
+
+    assert_ok_and_compare(&DefaultPath { port: 8080 });
+
+    set_env("dotenv_path", "./tests/data/other.env");
+    assert_ok_and_compare(&DefaultPath { port: 9090 });
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        dotenv(clap(long = "env-file"), env = "dotenv_path"),
+        __debug_cmd_input__("--env-file=./tests/data/app.env")
+    )]
+    struct Clap {
+        #[source(env = "dotenv_port")]
+        port: u16,
+    }
+
+    assert_ok_and_compare(&Clap { port: 8080 });
+}
+
+fn missing_dotenv() {
+    #[derive(Debug, PartialEq)]
+    #[config(dotenv = "./tests/data/missing.env", __debug_cmd_input__())]
+    struct Optional {
+        #[source(env = "dotenv_port", default = 80)]
+        port: u16,
+    }
+
+    assert_ok_and_compare(&Optional { port: 80 });
+
+    #[derive(Debug)]
+    #[config(dotenv(default = "./tests/data/missing.env"), __debug_cmd_input__())]
+    struct Required {
+        #[source(env = "dotenv_port", default = 80)]
+        _port: u16,
+    }
+
+    assert!(Required::parse().is_err());
+}
+
+#[test]
+fn dotenv() {
+    test_env(vec![dotenv_syntax, dotenv_path, missing_dotenv]);
+}
@@ -49,15 +49,14 @@ fn optional_var() {
 }
 
 fn optional_var_not_found() {
+    #[derive(Debug, PartialEq)]
     #[config(__debug_cmd_input__())]
     struct OptionalVarConfig {
-        #[allow(dead_code)]
         #[source(env = "a")]
         a: Option<String>,
     }
 
-    let parsed = OptionalVarConfig::parse();
-    assert!(matches!(parsed, Err(Error::MissingArgument(_))));
+    assert_ok_and_compare(&OptionalVarConfig { a: None });
 }
 
 #[test]
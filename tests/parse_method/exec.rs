@@ -0,0 +1,74 @@
+use config_manager::{config, ConfigInit, Error};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+fn command_output() {
+    #[derive(Debug, PartialEq)]
+    #[config(__debug_cmd_input__())]
+    struct Output {
+        #[source(exec = ["echo", "  hello  "])]
+        greeting: String,
+        #[source(exec(command = ["printf", "8080\n\n"], timeout = 5))]
+        port: u16,
+    }
+
+    assert_ok_and_compare(&Output {
+        greeting: "hello".into(),
+        port: 8080,
+    });
+}
+
+fn ordering() {
+    #[derive(Debug, PartialEq)]
+    #[config(__debug_cmd_input__())]
+    struct Ordering {
+        #[source(env = "exec_port", exec = ["false"], default = 80)]
+        port: u16,
+    }
+
+    set_env("exec_port", 9000);
+    assert_ok_and_compare(&Ordering { port: 9000 });
+}
+
+fn failures() {
+    #[derive(Debug)]
+    #[config(__debug_cmd_input__())]
+    struct ExitCode {
+        #[source(exec = ["sh", "-c", "echo oops >&2; exit 3"])]
+        _value: String,
+    }
+
+    match ExitCode::parse() {
+        Err(Error::ExecFailed { command, message }) => {
+            assert_eq!(command, ["sh", "-c", "echo oops >&2; exit 3"]);
+            assert!(message.contains("oops"), "{message}");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    #[derive(Debug)]
+    #[config(__debug_cmd_input__())]
+    struct NotFound {
+        #[source(exec = ["config-manager-missing-helper"])]
+        _value: String,
+    }
+
+    assert!(matches!(NotFound::parse(), Err(Error::ExecFailed { .. })));
+
+    #[derive(Debug)]
+    #[config(__debug_cmd_input__())]
+    struct Timeout {
+        #[source(exec(command = ["sleep", "5"], timeout = 1))]
+        _value: String,
+    }
+
+    match Timeout::parse() {
+        Err(Error::ExecFailed { message, .. }) => assert!(message.contains("timed out")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn exec() {
+    test_env(vec![command_output, ordering, failures]);
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use config_manager::{config, ConfigInit};
 use serde::Deserialize;
@@ -101,6 +101,186 @@ fn optional() {
     assert!(Optional::parse().is_ok());
 }
 
+fn default_paths() {
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(
+            format = "toml",
+            default = [
+                "./tests/data/fake.toml",
+                "${default_config_dir}/multiple/site.toml",
+                "./tests/data/multiple/base.toml",
+            ]
+        ),
+        __debug_cmd_input__()
+    )]
+    struct Fallback {
+        #[source(config)]
+        port: u16,
+    }
+
+    assert_ok_and_compare(&Fallback { port: 80 });
+
+    set_env("default_config_dir", "./tests/data");
+    assert_ok_and_compare(&Fallback { port: 8080 });
+
+    #[config(
+        file(
+            format = "toml",
+            default = ["./tests/data/fake.toml", "${unset_config_dir}/fake.toml"]
+        ),
+        __debug_cmd_input__()
+    )]
+    #[derive(Debug)]
+    struct NotFound {}
+
+    match NotFound::parse() {
+        Err(config_manager::Error::ExternalError(msg)) => assert_eq!(
+            msg,
+            "no default configuration file found: `./tests/data/fake.toml` does not exist, \
+             `${unset_config_dir}/fake.toml` is skipped: variable `unset_config_dir` is not set"
+        ),
+        res => panic!("unexpected result: {res:?}"),
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(format = "toml", default = "./tests/data/fake.toml", optional),
+        __debug_cmd_input__()
+    )]
+    struct Optional {
+        #[source(config, default = 1)]
+        port: u16,
+    }
+
+    assert_ok_and_compare(&Optional { port: 1 });
+}
+
+fn multiple() {
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(format = "toml", clap(long = "config"), multiple),
+        __debug_cmd_input__(
+            "--config=./tests/data/multiple/base.toml",
+            "--config=./tests/data/multiple/site.toml"
+        )
+    )]
+    struct ClapMultiple {
+        #[source(config)]
+        name: String,
+        #[source(config)]
+        port: u16,
+        #[source(config = "server.host")]
+        server_host: String,
+        #[source(config = "server.port")]
+        server_port: u16,
+    }
+
+    // Tables are merged deeply: only the port of the server is overridden by the later file
+    assert_ok_and_compare(&ClapMultiple {
+        name: "base".into(),
+        port: 8080,
+        server_host: "base.local".into(),
+        server_port: 8080,
+    });
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(format = "toml", env = "multiple_config", multiple),
+        __debug_cmd_input__()
+    )]
+    struct EnvMultiple {
+        #[source(config)]
+        name: String,
+        #[source(config)]
+        port: u16,
+    }
+
+    let paths = std::env::join_paths([
+        "./tests/data/multiple/site.toml",
+        "./tests/data/multiple/base.toml",
+    ])
+    .unwrap();
+    set_env("multiple_config", paths.to_string_lossy());
+    assert_ok_and_compare(&EnvMultiple {
+        name: "base".into(),
+        port: 80,
+    });
+}
+
+fn directory() {
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(format = "toml", default = "./tests/data/multiple/base.toml"),
+        file(dir, env = "conf_dir", default = "./tests/data/conf.d"),
+        __debug_cmd_input__()
+    )]
+    struct DropIns {
+        #[source(config)]
+        name: String,
+        #[source(config)]
+        port: u16,
+        #[source(config)]
+        host: String,
+        #[source(config = "db.url")]
+        db_url: String,
+        #[source(config = "db.pool")]
+        db_pool: u32,
+    }
+
+    // Drop-ins are merged deeply: the pool is added to the db table of the main file
+    assert_ok_and_compare(&DropIns {
+        name: "base".into(),
+        port: 9000,
+        host: "example.com".into(),
+        db_url: "postgres://base/app".into(),
+        db_pool: 8,
+    });
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(dir, env = "conf_dir", default = "./tests/data/conf.d"),
+        __debug_cmd_input__()
+    )]
+    struct EnvDir {
+        #[source(config)]
+        port: u16,
+    }
+
+    set_env("conf_dir", "./tests/data/multiple");
+    assert_ok_and_compare(&EnvDir { port: 8080 });
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(dir, glob = "*.toml", default = "./tests/data/conf.d"),
+        __debug_cmd_input__()
+    )]
+    struct Glob {
+        #[source(config)]
+        host: String,
+    }
+
+    assert_ok_and_compare(&Glob {
+        host: "localhost".into(),
+    });
+
+    #[config(file(dir, default = "./tests/data/fake.d"), __debug_cmd_input__())]
+    struct NotFound {}
+
+    assert!(matches!(
+        NotFound::parse(),
+        Err(config_manager::Error::ExternalError(_))
+    ));
+
+    #[config(
+        file(dir, default = "./tests/data/fake.d", optional),
+        __debug_cmd_input__()
+    )]
+    struct Optional {}
+
+    assert!(Optional::parse().is_ok());
+}
+
 fn solo_json() {
     #[derive(Debug, Deserialize, PartialEq)]
     enum Foo {
@@ -315,12 +495,147 @@ fn merged_configs() {
     });
 }
 
+fn discover() {
+    #[derive(Debug, PartialEq)]
+    #[config(file(discover = "myapp", env = "myapp_config"), __debug_cmd_input__())]
+    struct Discovered {
+        #[source(config)]
+        name: String,
+        #[source(config)]
+        port: u16,
+        #[config_files]
+        files: Vec<PathBuf>,
+    }
+
+    set_env("XDG_CONFIG_HOME", "./tests/data/xdg");
+    assert_ok_and_compare(&Discovered {
+        name: "discovered".into(),
+        port: 7000,
+        files: vec!["./tests/data/xdg/myapp/myapp.toml".into()],
+    });
+
+    set_env("myapp_config", "./tests/data/multiple/base.toml");
+    assert_ok_and_compare(&Discovered {
+        name: "base".into(),
+        port: 80,
+        files: vec!["./tests/data/multiple/base.toml".into()],
+    });
+
+    #[derive(Debug, PartialEq)]
+    #[config(file(discover = "myapp", format = "yaml"), __debug_cmd_input__())]
+    struct WrongFormat {
+        #[source(config)]
+        name: Option<String>,
+    }
+
+    assert!(WrongFormat::parse().is_err());
+}
+
+fn search_upwards() {
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(search_upwards = "Cargo.toml"),
+        table = "package",
+        __debug_cmd_input__()
+    )]
+    struct Nearest {
+        #[source(config)]
+        name: String,
+        #[config_files]
+        files: Vec<PathBuf>,
+    }
+
+    let manifest = std::env::current_dir().unwrap().join("Cargo.toml");
+    assert_ok_and_compare(&Nearest {
+        name: "config-manager".into(),
+        files: vec![manifest.clone()],
+    });
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(search_upwards = "Cargo.toml", format = "toml", multiple),
+        __debug_cmd_input__()
+    )]
+    struct All {
+        #[config_files]
+        files: Vec<PathBuf>,
+    }
+
+    let all = All::parse().unwrap();
+    assert_eq!(all.files.last(), Some(&manifest));
+    assert!(all.files.iter().all(|path| path.ends_with("Cargo.toml")));
+
+    #[derive(Debug, PartialEq)]
+    #[config(
+        file(search_upwards = ".no-such-apprc.toml", optional),
+        __debug_cmd_input__()
+    )]
+    struct NotFound {
+        #[source(config, default)]
+        name: Option<String>,
+        #[config_files]
+        files: Vec<PathBuf>,
+    }
+
+    assert_ok_and_compare(&NotFound {
+        name: None,
+        files: vec![],
+    });
+
+    #[config(file(search_upwards = ".no-such-apprc.toml"), __debug_cmd_input__())]
+    struct Required {}
+
+    assert!(Required::parse().is_err());
+}
+
+fn relative_to_file() {
+    #[derive(Debug, PartialEq)]
+    #[config(file(format = "toml", env = "relative_config"), __debug_cmd_input__())]
+    struct Paths {
+        #[source(config, relative_to_file)]
+        cert: PathBuf,
+        #[source(config = "cert")]
+        raw_cert: PathBuf,
+        #[source(config, relative_to_file)]
+        key: PathBuf,
+        #[source(config = "tls.ca", relative_to_file)]
+        ca: Option<PathBuf>,
+        #[source(env = "relative_env_cert", config = "cert", relative_to_file)]
+        env_cert: PathBuf,
+    }
+
+    set_env("relative_config", "./tests/data/relative/app.toml");
+    set_env("relative_env_cert", "certs/env.pem");
+    assert_ok_and_compare(&Paths {
+        cert: "./tests/data/relative/certs/server.pem".into(),
+        raw_cert: "certs/server.pem".into(),
+        key: "/etc/app/key.pem".into(),
+        ca: Some("./tests/data/relative/ca.pem".into()),
+        env_cert: "certs/env.pem".into(),
+    });
+}
+
 #[test]
 fn field_from_file() {
-    test_env(vec![solo_json, toml_solo, yaml_solo, merged_configs]);
+    test_env(vec![
+        solo_json,
+        toml_solo,
+        yaml_solo,
+        merged_configs,
+        relative_to_file,
+    ]);
 }
 
 #[test]
 fn file_attribute() {
-    test_env(vec![optional, file_not_found, file_found]);
+    test_env(vec![
+        optional,
+        file_not_found,
+        file_found,
+        default_paths,
+        multiple,
+        directory,
+        discover,
+        search_upwards,
+    ]);
 }
@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use config_manager::{config, ConfigInit};
+
+use crate::{assert_ok_and_compare, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/include/main.toml"),
+    __debug_cmd_input__()
+)]
+struct Included {
+    #[source(config)]
+    name: String,
+    #[source(config)]
+    level: String,
+    #[source(config = "server.port")]
+    port: u16,
+    #[source(config = "server.host", default = "none")]
+    host: String,
+    #[source(config = "db.url")]
+    db_url: String,
+    #[source(config = "db.pool", default)]
+    db_pool: u32,
+    #[source(config)]
+    timeout: u32,
+    #[source(config, default)]
+    include: Vec<String>,
+    #[config_files]
+    files: Vec<PathBuf>,
+}
+
+fn include() {
+    let dir = PathBuf::from("./tests/data/include");
+    assert_ok_and_compare(&Included {
+        name: "main".into(),
+        level: "info".into(),
+        port: 8080,
+        // Tables are merged deeply, values of the including file win
+        host: "0.0.0.0".into(),
+        db_url: "postgres://localhost/app".into(),
+        db_pool: 4,
+        timeout: 30,
+        include: vec![],
+        files: vec![
+            dir.join("common.toml"),
+            dir.join("sub").join("defaults.json"),
+            dir.join("sub/db.yaml"),
+            dir.join("main.toml"),
+        ],
+    });
+}
+
+#[derive(Debug)]
+#[config(
+    file(format = "toml", default = "./tests/data/include/cycle_a.toml"),
+    __debug_cmd_input__()
+)]
+struct Cycle {}
+
+fn cycle() {
+    let err = Cycle::parse().unwrap_err().to_string();
+    assert!(
+        err.starts_with("cyclic include of configuration files: "),
+        "{err}"
+    );
+    assert_eq!(err.matches("cycle_a.toml").count(), 2, "{err}");
+}
+
+#[test]
+fn include_files() {
+    test_env(vec![include, cycle]);
+}
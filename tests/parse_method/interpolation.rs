@@ -0,0 +1,180 @@
+use config_manager::{config, ConfigInit, Flatten};
+use serde::Deserialize;
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "itp",
+    file(format = "toml", default = "./tests/data/interpolation.toml"),
+    interpolate,
+    __debug_cmd_input__()
+)]
+struct Env {
+    #[source(config)]
+    url: String,
+    #[source(config)]
+    price: String,
+    #[source(env, default = "none")]
+    greeting: String,
+}
+
+fn env_variables() {
+    set_env("ITP_DB_HOST", "localhost");
+    set_env("ITP_NAME", "world");
+    set_env("ITP_GREETING", "hello, ${ITP_NAME}!");
+    assert_ok_and_compare(&Env {
+        url: "postgres://localhost:5432/app".into(),
+        price: "$5 for nothing".into(),
+        greeting: "hello, world!".into(),
+    });
+
+    set_env("ITP_DB_PORT", "6432");
+    set_env("ITP_ITEM", "");
+    assert_ok_and_compare(&Env {
+        url: "postgres://localhost:6432/app".into(),
+        price: "$5 for ".into(),
+        greeting: "hello, world!".into(),
+    });
+
+    // Values of the variables are taken as is
+    set_env("ITP_NAME", "a$$b ${ITP_DB_PORT}");
+    assert_ok_and_compare(&Env {
+        url: "postgres://localhost:6432/app".into(),
+        price: "$5 for ".into(),
+        greeting: "hello, a$$b ${ITP_DB_PORT}!".into(),
+    });
+}
+
+fn undefined_variable() {
+    let err = Env::parse().unwrap_err().to_string();
+    assert_eq!(err, "variable ITP_DB_HOST used in field url is not defined");
+}
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+#[table = "db"]
+#[interpolate(config)]
+struct Db {
+    #[source(config)]
+    address: String,
+    #[source(config)]
+    ports: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "itp",
+    file(format = "toml", default = "./tests/data/interpolation.toml"),
+    __debug_cmd_input__()
+)]
+struct Keys {
+    #[flatten]
+    db: Db,
+    #[source(config = "db.name")]
+    name: String,
+    #[source(env, interpolate, default = "none")]
+    greeting: String,
+}
+
+fn config_keys() {
+    set_env(
+        "ITP_GREETING",
+        "hi from ${ITP_DB_NAME:-${ITP_HOST:-nowhere}}",
+    );
+    assert_ok_and_compare(&Keys {
+        db: Db {
+            address: "db.local/main".into(),
+            ports: vec!["80".into(), "443".into()],
+        },
+        name: "main".into(),
+        greeting: "hi from nowhere".into(),
+    });
+
+    set_env("ITP_PORT", "8080");
+    set_env("ITP_DB_NAME", "${ITP_PORT}");
+    assert_ok_and_compare(&Keys {
+        db: Db {
+            address: "db.local/main".into(),
+            ports: vec!["8080".into(), "443".into()],
+        },
+        name: "main".into(),
+        greeting: "hi from ${ITP_PORT}".into(),
+    });
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/interpolation.toml"),
+    __debug_cmd_input__()
+)]
+struct Cycle {
+    #[source(config, interpolate(config))]
+    cyclic: String,
+}
+
+fn cycle() {
+    let err = Cycle::parse().unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "cyclic variable reference in field cyclic: cycle.a -> cycle.b -> cycle.a"
+    );
+
+    // Environment values are not expanded, so they can't make a cycle
+    set_env("ITP_CYCLE", "${ITP_CYCLE}");
+    assert_ok_and_compare(&Cycle {
+        cyclic: "${ITP_CYCLE}".into(),
+    });
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/interpolation.toml"),
+    __debug_cmd_input__()
+)]
+struct Disabled {
+    #[source(config)]
+    undefined: String,
+}
+
+fn disabled() {
+    assert_ok_and_compare(&Disabled {
+        undefined: "${ITP_UNDEFINED}".into(),
+    });
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "itp",
+    secrets_dir = "./tests/data/secrets",
+    interpolate,
+    __debug_cmd_input__("--flag", "$${ITP_NAME}")
+)]
+struct OtherSources {
+    #[source(secret_file = "interpolated")]
+    password: String,
+    #[source(exec = ["echo", "$$ ${ITP_NAME"])]
+    output: String,
+    #[source(clap(long))]
+    flag: String,
+}
+
+fn other_sources_untouched() {
+    set_env("ITP_NAME", "world");
+    assert_ok_and_compare(&OtherSources {
+        password: "pa$$w0rd${X}".into(),
+        output: "$$ ${ITP_NAME".into(),
+        flag: "$${ITP_NAME}".into(),
+    });
+}
+
+#[test]
+fn interpolation() {
+    test_env(vec![
+        env_variables,
+        undefined_variable,
+        config_keys,
+        cycle,
+        disabled,
+        other_sources_untouched,
+    ]);
+}
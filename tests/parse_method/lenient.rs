@@ -0,0 +1,77 @@
+use config_manager::{config, ConfigInit};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "lnt",
+    lenient,
+    __debug_cmd_input__("--workers", " 0x10 ", "--fast", "ON")
+)]
+struct Lenient {
+    #[source(env, default = false)]
+    debug: bool,
+    #[source(env)]
+    verbose: Option<bool>,
+    #[source(clap(long, flag), default = false)]
+    fast: bool,
+    #[source(clap(long), default = 1)]
+    workers: u32,
+    #[source(env, default = 0)]
+    limit: i64,
+    #[source(env, default = "default")]
+    name: String,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "lnt", __debug_cmd_input__())]
+struct FieldLevel {
+    #[source(env, lenient)]
+    mask: u8,
+    #[source(env, default = false)]
+    strict: bool,
+}
+
+fn struct_level() {
+    set_env("LNT_DEBUG", "yes");
+    set_env("LNT_VERBOSE", " OFF ");
+    set_env("LNT_LIMIT", "-1_000");
+    set_env("LNT_NAME", "0x10");
+    assert_ok_and_compare(&Lenient {
+        debug: true,
+        verbose: Some(false),
+        fast: true,
+        workers: 16,
+        limit: -1000,
+        name: "0x10".into(),
+    });
+}
+
+fn invalid() {
+    set_env("LNT_DEBUG", "maybe");
+    let err = Lenient::parse().unwrap_err().to_string();
+    assert!(
+        err.starts_with("Can't deserialize from value: maybe of field debug"),
+        "{err}"
+    );
+}
+
+fn field_level() {
+    set_env("LNT_MASK", "0b1010");
+    assert_ok_and_compare(&FieldLevel {
+        mask: 10,
+        strict: false,
+    });
+
+    set_env("LNT_STRICT", "yes");
+    let err = FieldLevel::parse().unwrap_err().to_string();
+    assert!(
+        err.starts_with("Can't deserialize from value: yes of field strict"),
+        "{err}"
+    );
+}
+
+#[test]
+fn lenient() {
+    test_env(vec![struct_level, invalid, field_level]);
+}
@@ -0,0 +1,67 @@
+use config_manager::{config, ConfigInit, Flatten};
+use serde::Deserialize;
+
+use crate::{assert_ok_and_compare, test_env};
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+struct Output {
+    #[source(clap(long, flag, negatable), default = true)]
+    color: bool,
+}
+
+macro_rules! negatable {
+    ($name:ident, $($args:literal),*) => {
+        #[derive(Debug, PartialEq)]
+        #[config(
+            file(format = "toml", default = "./tests/data/negatable.toml"),
+            __debug_cmd_input__($($args),*)
+        )]
+        struct $name {
+            #[source(clap(long, short, flag, negatable), config)]
+            feature: bool,
+            #[flatten]
+            output: Output,
+        }
+    };
+}
+
+negatable!(NoArgs,);
+negatable!(Negated, "--no-feature", "--no-color");
+negatable!(NegatedThenSet, "--no-feature", "-f");
+negatable!(SetThenNegated, "--feature", "--no-feature");
+
+fn from_config() {
+    assert_ok_and_compare(&NoArgs {
+        feature: true,
+        output: Output { color: true },
+    });
+}
+
+fn negated() {
+    assert_ok_and_compare(&Negated {
+        feature: false,
+        output: Output { color: false },
+    });
+}
+
+fn last_wins() {
+    assert_ok_and_compare(&NegatedThenSet {
+        feature: true,
+        output: Output { color: true },
+    });
+    assert_ok_and_compare(&SetThenNegated {
+        feature: false,
+        output: Output { color: true },
+    });
+}
+
+fn help() {
+    let help = NoArgs::get_command().render_help().to_string();
+    assert!(help.contains("--no-feature"), "{help}");
+    assert!(help.contains("Sets --color to false"), "{help}");
+}
+
+#[test]
+fn negatable() {
+    test_env(vec![from_config, negated, last_wins, help]);
+}
@@ -0,0 +1,91 @@
+use config_manager::{config, ConfigInit};
+
+use crate::{set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "opt",
+    file(format = "yaml", env = "opt_config", optional),
+    __debug_cmd_input__()
+)]
+struct Optional {
+    #[source(clap(long), env, config)]
+    port: Option<u16>,
+    #[source(env, config)]
+    name: Option<String>,
+    #[source(env, config)]
+    timeout: Option<u32>,
+    #[source(env, default = Some(4))]
+    workers: Option<u32>,
+}
+
+fn absent() {
+    assert_eq!(
+        Optional::parse().unwrap(),
+        Optional {
+            port: None,
+            name: None,
+            timeout: None,
+            workers: Some(4),
+        }
+    );
+}
+
+fn present() {
+    set_env("OPT_PORT", "8080");
+    set_env("OPT_NAME", "app");
+    set_env("OPT_WORKERS", "2");
+    assert_eq!(
+        Optional::parse().unwrap(),
+        Optional {
+            port: Some(8080),
+            name: Some("app".into()),
+            timeout: None,
+            workers: Some(2),
+        }
+    );
+}
+
+fn null_and_empty() {
+    set_env("OPT_CONFIG", "./tests/data/optional.yaml");
+    set_env("OPT_WORKERS", "null");
+    assert_eq!(
+        Optional::parse().unwrap(),
+        Optional {
+            port: None,
+            name: None,
+            timeout: Some(30),
+            workers: None,
+        }
+    );
+
+    set_env("OPT_PORT", "");
+    set_env("OPT_NAME", " ");
+    set_env("OPT_WORKERS", "");
+    assert_eq!(
+        Optional::parse().unwrap(),
+        Optional {
+            port: None,
+            name: None,
+            timeout: Some(30),
+            workers: None,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "opt", __debug_cmd_input__("--port", ""))]
+struct EmptyArg {
+    #[source(clap(long), env)]
+    port: Option<u16>,
+}
+
+fn empty_clap() {
+    set_env("OPT_PORT", "8080");
+    assert_eq!(EmptyArg::parse().unwrap(), EmptyArg { port: None });
+}
+
+#[test]
+fn optional() {
+    test_env(vec![absent, present, null_and_empty, empty_clap]);
+}
@@ -48,7 +48,7 @@ fn test_config_source() {
     }
 
     let res = Config::parse_options(HashSet::from([
-        ConfigOption::ExplicitSource(Source::ConfigFiles(vec![FileOptions {
+        ConfigOption::ExplicitSource(Source::ConfigFiles(vec![FileOptions::File {
             format: config::FileFormat::Toml,
             path: "tests/data/config.toml".to_string(),
         }])),
@@ -58,6 +58,23 @@ fn test_config_source() {
 
     assert_eq!(res.field, 5);
     assert_eq!(res.int, 1);
+
+    #[config(file(format = "yaml", default = "not found"))]
+    struct DropIns {
+        #[source(config)]
+        port: u16,
+    }
+
+    let res = DropIns::parse_options(HashSet::from([
+        ConfigOption::ExplicitSource(Source::ConfigFiles(vec![FileOptions::Directory {
+            path: "tests/data/conf.d".to_string(),
+            glob: None,
+        }])),
+        ConfigOption::ExplicitSource(Source::Clap(ClapSource::None)),
+    ]))
+    .unwrap();
+
+    assert_eq!(res.port, 9000);
 }
 
 #[test]
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+
+use config_manager::{config, ClapSource, ConfigInit, ConfigOption, Source};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/profiles.toml"),
+    profiles(env = "prf_app_profile", clap(long = "profile"), default = "dev"),
+    __debug_cmd_input__()
+)]
+struct Profiles {
+    #[source(config)]
+    name: String,
+    #[source(config)]
+    debug: bool,
+    #[source(config = "server.host")]
+    host: String,
+    #[source(config = "server.port")]
+    port: u16,
+    #[config_profile]
+    profile: Option<String>,
+}
+
+fn default_profile() {
+    assert_ok_and_compare(&Profiles {
+        name: "app".into(),
+        debug: true,
+        host: "localhost".into(),
+        port: 8080,
+        profile: Some("dev".into()),
+    });
+}
+
+fn env_profile() {
+    // Tables are merged deeply: only the host is overridden
+    set_env("PRF_APP_PROFILE", "staging");
+    assert_ok_and_compare(&Profiles {
+        name: "app".into(),
+        debug: false,
+        host: "staging.local".into(),
+        port: 8080,
+        profile: Some("staging".into()),
+    });
+
+    set_env("PRF_APP_PROFILE", "prod");
+    assert_ok_and_compare(&Profiles {
+        name: "app-prod".into(),
+        debug: false,
+        host: "prod.local".into(),
+        port: 443,
+        profile: Some("prod".into()),
+    });
+}
+
+fn clap_profile() {
+    set_env("PRF_APP_PROFILE", "prod");
+    let cfg = Profiles::parse_options(HashSet::from([ConfigOption::ExplicitSource(Source::Clap(
+        ClapSource::Args(vec!["--profile".into(), "staging".into()]),
+    ))]))
+    .unwrap();
+    assert_eq!(cfg.host, "staging.local");
+}
+
+fn unknown_profile() {
+    set_env("PRF_APP_PROFILE", "qa");
+    assert_eq!(
+        Profiles::parse().unwrap_err().to_string(),
+        "unknown profile qa, available profiles: [dev, prod, staging]"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/profiles.toml"),
+    profiles(env = "prf_app_profile", default = "qa"),
+    __debug_cmd_input__()
+)]
+struct MissingDefault {
+    #[source(config)]
+    name: String,
+    #[config_profile]
+    profile: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/missing.toml", optional),
+    profiles(env = "prf_app_profile", default = "dev"),
+    __debug_cmd_input__()
+)]
+struct MissingFile {
+    #[source(config, default = "none")]
+    name: String,
+    #[config_profile]
+    profile: Option<String>,
+}
+
+fn missing_default_profile() {
+    assert_ok_and_compare(&MissingDefault {
+        name: "app".into(),
+        profile: None,
+    });
+    assert_ok_and_compare(&MissingFile {
+        name: "none".into(),
+        profile: None,
+    });
+
+    // Explicitly selected profile must exist
+    set_env("PRF_APP_PROFILE", "qa");
+    assert_eq!(
+        MissingDefault::parse().unwrap_err().to_string(),
+        "unknown profile qa, available profiles: [dev, prod, staging]"
+    );
+    assert_eq!(
+        MissingFile::parse().unwrap_err().to_string(),
+        "unknown profile qa, available profiles: []"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/profiles.toml"),
+    __debug_cmd_input__()
+)]
+struct WithoutProfiles {
+    #[source(config = "profile.prod.name")]
+    prod_name: String,
+}
+
+fn without_profiles() {
+    assert_ok_and_compare(&WithoutProfiles {
+        prod_name: "app-prod".into(),
+    });
+}
+
+#[test]
+fn profiles() {
+    test_env(vec![
+        default_profile,
+        env_profile,
+        clap_profile,
+        unknown_profile,
+        missing_default_profile,
+        without_profiles,
+    ]);
+}
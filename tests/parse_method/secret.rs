@@ -0,0 +1,136 @@
+use config_manager::{config, ConfigInit, Secret};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "app",
+    secrets_dir = "./tests/data/secrets",
+    __debug_cmd_input__()
+)]
+struct Secrets {
+    #[source(env, env_file, secret_file, default = "none")]
+    db_password: String,
+    #[source(env_file = "token", default)]
+    api_token: String,
+    #[source(secret_file = "missing", default = "fallback")]
+    missing: String,
+}
+
+fn secrets_dir() {
+    assert_ok_and_compare(&Secrets {
+        db_password: "s3cr3t".into(),
+        api_token: "".into(),
+        missing: "fallback".into(),
+    });
+}
+
+fn env_file() {
+    set_env("APP_DB_PASSWORD_FILE", "./tests/data/secrets/api_token");
+    set_env("TOKEN_FILE", "./tests/data/secrets/api_token");
+    assert_ok_and_compare(&Secrets {
+        db_password: "token-from-file".into(),
+        api_token: "token-from-file".into(),
+        missing: "fallback".into(),
+    });
+
+    set_env("APP_DB_PASSWORD", "plain");
+    assert_ok_and_compare(&Secrets {
+        db_password: "plain".into(),
+        api_token: "token-from-file".into(),
+        missing: "fallback".into(),
+    });
+}
+
+fn missing_env_file() {
+    set_env("TOKEN_FILE", "./tests/data/secrets/not_found");
+    assert!(Secrets::parse().is_err());
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "rdc", __debug_cmd_input__("--pin", "12x4"))]
+struct Redacted {
+    #[source(env, secret, interpolate)]
+    password: String,
+    #[source(clap(long), secret)]
+    pin: u32,
+    #[source(env, default)]
+    token: Secret<String>,
+}
+
+fn redacted_errors() {
+    set_env("RDC_PASSWORD", "hunter2");
+    assert_eq!(
+        Redacted::parse().unwrap_err().to_string(),
+        "Can't deserialize the secret value of field pin"
+    );
+
+    set_env("RDC_PASSWORD", "hunter2${unclosed");
+    let err = Redacted::parse().unwrap_err().to_string();
+    assert!(!err.contains("hunter2"), "{err}");
+    assert!(err.contains("***"), "{err}");
+}
+
+fn pin_is_not_sequential(pin: &u32) -> Result<(), String> {
+    if *pin == 1234 {
+        Err(format!("{pin} is sequential"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+#[config(__debug_cmd_input__("--pin", "1234"))]
+struct ValidatedSecret {
+    #[allow(dead_code)]
+    #[source(clap(long), secret, validate(with = pin_is_not_sequential))]
+    pin: u32,
+}
+
+fn redacted_validation() {
+    assert_eq!(
+        ValidatedSecret::parse().unwrap_err().to_string(),
+        "invalid value of field pin taken from command line: violates `with = pin_is_not_sequential`"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(__debug_cmd_input__())]
+struct DefaultInHelp {
+    #[source(clap(long), secret, default = 9876)]
+    pin: u32,
+}
+
+fn hidden_in_help() {
+    let help = DefaultInHelp::get_command().render_long_help().to_string();
+    assert!(help.contains("--pin <PIN>"), "{help}");
+    assert!(!help.contains("9876"), "{help}");
+    assert_ok_and_compare(&DefaultInHelp { pin: 9876 });
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "rdc", __debug_cmd_input__())]
+struct Wrapped {
+    #[source(env)]
+    token: Secret<String>,
+}
+
+fn secret_wrapper() {
+    set_env("RDC_TOKEN", "t0ken");
+    let wrapped = Wrapped::parse().unwrap();
+    assert_eq!(wrapped.token.expose(), "t0ken");
+    assert_eq!(format!("{wrapped:?}"), "Wrapped { token: Secret(***) }");
+}
+
+#[test]
+fn secret() {
+    test_env(vec![
+        secrets_dir,
+        env_file,
+        missing_env_file,
+        redacted_errors,
+        redacted_validation,
+        hidden_in_help,
+        secret_wrapper,
+    ]);
+}
@@ -0,0 +1,100 @@
+use config_manager::{config, ConfigInit, Flatten};
+use serde::Deserialize;
+
+use crate::{set_env, test_env};
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+#[validate(Self::check)]
+struct Tls {
+    #[source(env = "svl_tls", default)]
+    enabled: bool,
+    #[source(env = "svl_tls_cert", default)]
+    cert: Option<String>,
+}
+
+impl Tls {
+    fn check(&self) -> Result<(), &'static str> {
+        if self.enabled && self.cert.is_none() {
+            Err("tls_cert is required when tls is enabled")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+struct Limits {
+    #[source(env = "svl_port", default = 8080, validate(range = 1..))]
+    port: u32,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "svl", validate = Self::check, __debug_cmd_input__())]
+struct Server {
+    #[source(env, default = 1)]
+    min: u32,
+    #[source(env, default = 10)]
+    max: u32,
+    #[flatten]
+    tls: Tls,
+    #[flatten]
+    limits: Limits,
+}
+
+impl Server {
+    fn check(&self) -> Result<(), String> {
+        if self.min > self.max {
+            Err(format!(
+                "min ({}) is greater than max ({})",
+                self.min, self.max
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn valid() {
+    set_env("SVL_TLS", "true");
+    set_env("SVL_TLS_CERT", "\"cert.pem\"");
+    assert_eq!(
+        Server::parse().unwrap(),
+        Server {
+            min: 1,
+            max: 10,
+            tls: Tls {
+                enabled: true,
+                cert: Some("cert.pem".into())
+            },
+            limits: Limits { port: 8080 },
+        }
+    );
+}
+
+fn top_level() {
+    set_env("SVL_MIN", "20");
+    assert_eq!(
+        Server::parse().unwrap_err().to_string(),
+        "invalid configuration: min (20) is greater than max (10)"
+    );
+}
+
+fn nested() {
+    set_env("SVL_TLS", "true");
+    assert_eq!(
+        Server::parse().unwrap_err().to_string(),
+        "invalid configuration of tls: tls_cert is required when tls is enabled"
+    );
+
+    set_env("SVL_PORT", "0");
+    set_env("SVL_TLS", "false");
+    assert_eq!(
+        Server::parse().unwrap_err().to_string(),
+        "invalid value of field limits.port taken from env: violates `range = 1 ..`"
+    );
+}
+
+#[test]
+fn struct_validation() {
+    test_env(vec![valid, top_level, nested]);
+}
@@ -0,0 +1,91 @@
+use std::collections::{HashMap, HashSet};
+
+use config_manager::{config, ConfigInit, ConfigOption, Flatten};
+use serde::Deserialize;
+
+use crate::test_env;
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+#[table = "server"]
+struct Server {
+    #[source(config)]
+    port: u16,
+    #[source(config, default = "0.0.0.0")]
+    host: String,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/strict.toml"),
+    deny_unknown_keys,
+    __debug_cmd_input__()
+)]
+struct Strict {
+    #[source(config)]
+    name: String,
+    #[source(config, default = 1)]
+    prediction_delay: u32,
+    #[flatten]
+    server: Server,
+    #[source(config, default)]
+    limits: HashMap<String, i32>,
+}
+
+fn unknown_keys() {
+    let file = "./tests/data/strict.toml";
+    assert_eq!(
+        Strict::parse().unwrap_err().to_string(),
+        format!(
+            "unknown keys in configuration files: `extra` in {file}, \
+            `prediction_dealy` in {file} (did you mean `prediction_delay`?), \
+            `server.hots` in {file} (did you mean `server.host`?)"
+        )
+    );
+}
+
+fn option_overrides_attribute() {
+    let strict = Strict::parse_options(HashSet::from([ConfigOption::DenyUnknownKeys(false)]));
+    assert_eq!(
+        strict.unwrap(),
+        Strict {
+            name: "app".into(),
+            prediction_delay: 1,
+            server: Server {
+                port: 80,
+                host: "0.0.0.0".into()
+            },
+            limits: HashMap::from([("max".into(), 10), ("min".into(), 1)]),
+        }
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    file(format = "toml", default = "./tests/data/strict.toml"),
+    table = "limits",
+    __debug_cmd_input__()
+)]
+struct Limits {
+    #[source(config)]
+    max: i32,
+}
+
+fn runtime_option() {
+    assert!(Limits::parse().is_ok());
+
+    let err = Limits::parse_options(HashSet::from([ConfigOption::DenyUnknownKeys(true)]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("`limits.min` in"), "{err}");
+    assert!(err.contains("`server.port` in"), "{err}");
+    assert!(!err.contains("`limits.max` in"), "{err}");
+}
+
+#[test]
+fn deny_unknown_keys() {
+    test_env(vec![
+        unknown_keys,
+        option_overrides_attribute,
+        runtime_option,
+    ]);
+}
@@ -0,0 +1,108 @@
+use config_manager::{config, ConfigInit, Flatten};
+use serde::Deserialize;
+
+use crate::{set_env, test_env};
+
+#[derive(Debug, PartialEq, Deserialize, Flatten)]
+struct Nested {
+    #[source(env, default)]
+    level: String,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "uev",
+    unused_env = "deny",
+    file(format = "toml", env = "uev_config", optional),
+    __debug_cmd_input__()
+)]
+struct Deny {
+    #[source(env, default)]
+    prediction_delay: u32,
+    #[flatten]
+    nested: Nested,
+    #[source(env_file, default)]
+    token: String,
+}
+
+fn consumed() {
+    set_env("UEV_PREDICTION_DELAY", "5");
+    set_env("UEV_LEVEL", "debug");
+    set_env("UEV_CONFIG", "./tests/data/config.toml");
+    set_env("OTHER_PREDICTON_DELAY", "5");
+    assert_eq!(
+        Deny::parse().unwrap(),
+        Deny {
+            prediction_delay: 5,
+            nested: Nested {
+                level: "debug".into()
+            },
+            token: "".into(),
+        }
+    );
+}
+
+fn unused() {
+    set_env("UEV_PREDICTON_DELAY", "5");
+    set_env("UEV_UNKNOWN", "1");
+    assert_eq!(
+        Deny::parse().unwrap_err().to_string(),
+        "unused environment variables: `UEV_PREDICTON_DELAY` (did you mean `UEV_PREDICTION_DELAY`?), \
+        `UEV_UNKNOWN`"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "uev", unused_env = "warn", __debug_cmd_input__())]
+struct Warn {
+    #[source(env, default)]
+    prediction_delay: u32,
+    #[unused_env]
+    unused: Vec<String>,
+}
+
+fn warn() {
+    set_env("UEV_PREDICTON_DELAY", "5");
+    set_env("UEV_UNKNOWN", "1");
+    assert_eq!(
+        Warn::parse().unwrap(),
+        Warn {
+            prediction_delay: 0,
+            unused: vec!["UEV_PREDICTON_DELAY".into(), "UEV_UNKNOWN".into()],
+        }
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "uev", __debug_cmd_input__())]
+struct FieldOnly {
+    #[source(env, default)]
+    prediction_delay: u32,
+    #[unused_env]
+    unused: Vec<String>,
+}
+
+fn field_only() {
+    set_env("UEV_PREDICTION_DELAY", "5");
+    assert_eq!(
+        FieldOnly::parse().unwrap(),
+        FieldOnly {
+            prediction_delay: 5,
+            unused: vec![],
+        }
+    );
+
+    set_env("UEV_LEVEL", "debug");
+    assert_eq!(
+        FieldOnly::parse().unwrap(),
+        FieldOnly {
+            prediction_delay: 5,
+            unused: vec!["UEV_LEVEL".into()],
+        }
+    );
+}
+
+#[test]
+fn unused_env() {
+    test_env(vec![consumed, unused, warn, field_only]);
+}
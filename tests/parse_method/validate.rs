@@ -0,0 +1,151 @@
+use config_manager::{config, ConfigInit};
+
+use crate::{assert_ok_and_compare, set_env, test_env};
+
+fn not_localhost(host: &String) -> Result<(), String> {
+    if host == "localhost" {
+        Err("localhost is not allowed".into())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "vld", __debug_cmd_input__())]
+struct Validated {
+    #[source(env, default = 8080, validate(range = 1..=65535))]
+    port: u32,
+    #[source(env, default = "0.0.0.0", validate(min_len = 1, with = not_localhost))]
+    host: String,
+    #[source(env, default = "info", validate(regex = "^(debug|info|warn|error)$"))]
+    level: String,
+    #[source(env, default, validate(max_len = 2))]
+    peers: Vec<String>,
+}
+
+fn valid() {
+    set_env("VLD_PORT", "443");
+    set_env("VLD_PEERS", "[\"a\", \"b\"]");
+    assert_ok_and_compare(&Validated {
+        port: 443,
+        host: "0.0.0.0".into(),
+        level: "info".into(),
+        peers: vec!["a".into(), "b".into()],
+    });
+}
+
+fn range() {
+    set_env("VLD_PORT", "0");
+    assert_eq!(
+        Validated::parse().unwrap_err().to_string(),
+        "invalid value of field port taken from env: violates `range = 1 ..= 65535`"
+    );
+}
+
+fn len() {
+    set_env("VLD_HOST", "");
+    assert_eq!(
+        Validated::parse().unwrap_err().to_string(),
+        "invalid value of field host taken from env: violates `min_len = 1`"
+    );
+
+    set_env("VLD_HOST", "h");
+    set_env("VLD_PEERS", "[\"a\", \"b\", \"c\"]");
+    assert_eq!(
+        Validated::parse().unwrap_err().to_string(),
+        "invalid value of field peers taken from env: violates `max_len = 2`"
+    );
+}
+
+fn regex() {
+    set_env("VLD_LEVEL", "trace");
+    assert_eq!(
+        Validated::parse().unwrap_err().to_string(),
+        "invalid value of field level taken from env: \
+        violates `regex = \"^(debug|info|warn|error)$\"`"
+    );
+}
+
+fn with() {
+    set_env("VLD_HOST", "localhost");
+    assert_eq!(
+        Validated::parse().unwrap_err().to_string(),
+        "invalid value of field host taken from env: \
+        violates `with = not_localhost`: localhost is not allowed"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(__debug_cmd_input__())]
+struct InvalidDefault {
+    #[source(default = 0, validate(range = 1..))]
+    workers: u32,
+}
+
+fn default() {
+    assert_eq!(
+        InvalidDefault::parse().unwrap_err().to_string(),
+        "invalid value of field workers taken from default: violates `range = 1 ..`"
+    );
+}
+
+fn short_name(name: &str) -> Result<(), String> {
+    if name.len() > 8 {
+        Err("too long".into())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[config(env_prefix = "vld", __debug_cmd_input__())]
+struct OptionalValidated {
+    #[source(env, validate(range = 1..=10))]
+    workers: Option<u16>,
+    #[source(env, validate(min_len = 1, max_len = 2))]
+    tags: Option<Vec<String>>,
+    #[source(env, validate(regex = "^[a-z]+$", with = short_name))]
+    name: Option<String>,
+}
+
+fn optional() {
+    assert_ok_and_compare(&OptionalValidated {
+        workers: None,
+        tags: None,
+        name: None,
+    });
+
+    set_env("VLD_WORKERS", "4");
+    set_env("VLD_TAGS", "[\"a\"]");
+    set_env("VLD_NAME", "app");
+    assert_ok_and_compare(&OptionalValidated {
+        workers: Some(4),
+        tags: Some(vec!["a".into()]),
+        name: Some("app".into()),
+    });
+
+    set_env("VLD_WORKERS", "11");
+    assert_eq!(
+        OptionalValidated::parse().unwrap_err().to_string(),
+        "invalid value of field workers taken from env: violates `range = 1 ..= 10`"
+    );
+
+    envmnt::remove("VLD_WORKERS");
+    set_env("VLD_TAGS", "[]");
+    assert_eq!(
+        OptionalValidated::parse().unwrap_err().to_string(),
+        "invalid value of field tags taken from env: violates `min_len = 1`"
+    );
+
+    envmnt::remove("VLD_TAGS");
+    set_env("VLD_NAME", "application");
+    assert_eq!(
+        OptionalValidated::parse().unwrap_err().to_string(),
+        "invalid value of field name taken from env: violates `with = short_name`: too long"
+    );
+}
+
+#[test]
+fn validate() {
+    test_env(vec![valid, range, len, regex, with, default, optional]);
+}
@@ -0,0 +1,188 @@
+use config_manager::{config, ConfigInit};
+use serde::Deserialize;
+
+use crate::{set_env, test_env};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    Single,
+    Cluster,
+}
+
+#[derive(Debug, PartialEq)]
+#[config(__debug_cmd_input__("--port", "8080", "--mode", "cluster", "--timeout", ""))]
+struct Typed {
+    #[source(clap(long))]
+    port: u16,
+    #[source(clap(long))]
+    mode: Mode,
+    #[source(clap(long))]
+    timeout: Option<u32>,
+    #[source(clap(long), default)]
+    name: String,
+}
+
+fn valid() {
+    assert_eq!(
+        Typed::parse().unwrap(),
+        Typed {
+            port: 8080,
+            mode: Mode::Cluster,
+            timeout: None,
+            name: "".into(),
+        }
+    );
+}
+
+fn help() {
+    let help = Typed::get_command().render_help().to_string();
+    assert!(help.contains("--port <PORT>"), "{help}");
+    assert!(help.contains("--timeout <TIMEOUT>"), "{help}");
+    assert!(help.contains("--mode <MODE>"), "{help}");
+    assert!(
+        help.contains("[possible values: single, cluster]"),
+        "{help}"
+    );
+}
+
+#[derive(Debug)]
+#[config(__debug_cmd_input__("--port", "abc"))]
+struct InvalidPort {
+    #[allow(dead_code)]
+    #[source(clap(long))]
+    port: u16,
+}
+
+fn invalid_port() {
+    let err = InvalidPort::parse().unwrap_err().to_string();
+    assert!(
+        err.starts_with(
+            "failed to parse command line: error: invalid value 'abc' for '--port <PORT>': \
+            invalid digit found in string"
+        ),
+        "{err}"
+    );
+}
+
+#[derive(Debug)]
+#[config(__debug_cmd_input__("--mode", "mesh"))]
+struct InvalidMode {
+    #[allow(dead_code)]
+    #[source(clap(long))]
+    mode: Mode,
+}
+
+fn invalid_mode() {
+    let err = InvalidMode::parse().unwrap_err().to_string();
+    assert!(
+        err.contains("invalid value 'mesh' for '--mode <MODE>'"),
+        "{err}"
+    );
+    assert!(err.contains("[possible values: single, cluster]"), "{err}");
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum LogFormat {
+    Json,
+    PlainText,
+    #[serde(rename = "ecs")]
+    ElasticCommonSchema,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Target {
+    Stdout,
+    File(String),
+}
+
+#[derive(Debug, PartialEq)]
+#[config(
+    env_prefix = "vps",
+    file(format = "toml", env = "vps_config", optional),
+    __debug_cmd_input__("--log-format", "plain-text", "--target", "{File: \"log.txt\"}")
+)]
+struct SerdeEnum {
+    #[source(clap(long = "log-format"), env, config)]
+    log_format: LogFormat,
+    #[source(clap(long), default = Target::Stdout)]
+    target: Target,
+}
+
+fn serde_enum() {
+    assert_eq!(
+        SerdeEnum::parse().unwrap(),
+        SerdeEnum {
+            log_format: LogFormat::PlainText,
+            target: Target::File("log.txt".into()),
+        }
+    );
+
+    let help = SerdeEnum::get_command().render_help().to_string();
+    assert!(
+        help.contains("[possible values: json, plain-text, ecs]"),
+        "{help}"
+    );
+    assert_eq!(help.matches("possible values").count(), 1, "{help}");
+}
+
+#[derive(Debug)]
+#[config(
+    env_prefix = "vps",
+    file(format = "toml", env = "vps_config", optional),
+    __debug_cmd_input__()
+)]
+struct SerdeEnumNoClap {
+    #[allow(dead_code)]
+    #[source(env, config)]
+    log_format: LogFormat,
+}
+
+fn serde_enum_env_and_config() {
+    set_env("VPS_LOG_FORMAT", "yaml");
+    assert_eq!(
+        SerdeEnumNoClap::parse().unwrap_err().to_string(),
+        "Can't deserialize from value: yaml of field log_format; expected one of json, plain-text, ecs"
+    );
+
+    envmnt::remove("VPS_LOG_FORMAT");
+    set_env("VPS_CONFIG", "./tests/data/serde_enum.toml");
+    assert_eq!(
+        SerdeEnumNoClap::parse().unwrap_err().to_string(),
+        "Can't deserialize from value: \"xml\" of field log_format; expected one of json, plain-text, ecs"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[config(__debug_cmd_input__("--extra", "foo", "--tags", "[\"a\", \"b\"]"))]
+struct JsonValue {
+    #[source(clap(long))]
+    extra: serde_json::Value,
+    #[source(clap(long))]
+    tags: serde_json::Value,
+}
+
+// `FromStr` of `serde_json::Value` rejects the unquoted strings the deserialization accepts
+fn json_value() {
+    assert_eq!(
+        JsonValue::parse().unwrap(),
+        JsonValue {
+            extra: serde_json::json!("foo"),
+            tags: serde_json::json!(["a", "b"]),
+        }
+    );
+}
+
+#[test]
+fn value_parser() {
+    test_env(vec![
+        valid,
+        help,
+        invalid_port,
+        invalid_mode,
+        serde_enum,
+        serde_enum_env_and_config,
+        json_value,
+    ]);
+}